@@ -1,6 +1,12 @@
+#![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use anchor_lang::solana_program::pubkey;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, spl_token_2022::instruction::AuthorityType, Mint, TokenAccount, TokenInterface,
+};
+use token_contract::{math, FeeRecipient, MAX_FEE_RECIPIENTS};
+use anchor_lang::solana_program::{keccak, pubkey};
+use anchor_lang::{system_program, Discriminator};
 
 // Code ka Unique ID (Program ID). Ye deploy karne ke baad milta hai.
 declare_id!("9vF8iR37L3nKtBR4x6mhy8dE8eMLUzcuCNbSCGCpnYHG");
@@ -9,6 +15,31 @@ declare_id!("9vF8iR37L3nKtBR4x6mhy8dE8eMLUzcuCNbSCGCpnYHG");
 // Ye hardcoded address hai, sirf yahi banda initially setup kar payega.
 const ADMIN_PUBKEY: Pubkey = pubkey!("HfLwDVax4RaftkctDGGw5a84jheVZtSint919Xy9D3dD");
 
+// Emission schedule me maximum kitne (start_ts, rate) segments ho sakte hain.
+// Isi se GlobalConfig ka space fix hota hai.
+pub const MAX_EMISSION_SEGMENTS: usize = 16;
+
+// Reward per share ko is factor se scale karke store karte hain taaki
+// chhote stakes ke liye bhi precision na khoye.
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+// Crank bounty ki upper limit (5%), taaki cranker user ka reward na kha jaye.
+pub const MAX_COMPOUND_BOUNTY_BPS: u64 = 500;
+
+// Boost tiers ki max ginti aur multiplier ki range (10000 = 1x, 30000 = 3x).
+pub const MAX_BOOST_TIERS: usize = 8;
+pub const MAX_BOOST_MULTIPLIER_BPS: u64 = 30_000;
+
+// Metaplex Token Metadata program; collection boost tiers iske metadata account se NFT ka collection padhte hain
+pub const METADATA_PROGRAM_ID: Pubkey = anchor_lang::solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+const METADATA_KEY_V1: u8 = 4;
+
+// Loyalty discount tiers ki max ginti.
+pub const MAX_LOYALTY_TIERS: usize = 8;
+
+// Merkle distributor ek round me kitne claims track kar sakta hai (bitmap = iska /8 bytes).
+pub const MAX_DISTRIBUTOR_CLAIMS: usize = 8192;
+
 #[program]
 pub mod staking_contract {
     use super::*;
@@ -26,6 +57,8 @@ pub mod staking_contract {
         let config = &mut ctx.accounts.config;
         config.admin = ADMIN_PUBKEY;
         config.withdraw_fee_bps = initial_fee_bps;
+        config.fee_vault = ctx.accounts.fee_vault.key();
+        config.last_update_ts = Clock::get()?.unix_timestamp;
 
         msg!("Staking Vault & Config Initialized! Initial Fee: {} bps", initial_fee_bps);
         Ok(())
@@ -49,18 +82,79 @@ pub mod staking_contract {
         Ok(())
     }
 
+    // Set Fee Vault: Admin fee ka default account badalta hai. Withdraw me sirf yahi account chalega,
+    // warna staker apni fee khud ko hi bhej deta.
+    pub fn set_fee_vault(ctx: Context<SetFeeVault>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.fee_vault = ctx.accounts.fee_vault.key();
+
+        emit!(FeeVaultUpdated {
+            fee_vault: config.fee_vault,
+        });
+
+        msg!("Fee vault set to {}", config.fee_vault);
+        Ok(())
+    }
+
+    // Initialize Rewards: Admin launch pe reward mint aur emission schedule commit karta hai.
+    // Schedule ek baar set hone ke baad change nahi hota, taaki tokenomics on-chain locked rahe.
+    pub fn initialize_rewards(
+        ctx: Context<InitializeRewards>,
+        segments: Vec<EmissionSegment>,
+        halving_interval: i64,
+    ) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // 1. Schedule sirf ek baar commit ho sakta hai
+        require_keys_eq!(config.reward_mint, Pubkey::default(), ErrorCode::RewardsAlreadyInitialized);
+
+        // 2. Schedule validate karo (non-empty, sorted, limit ke andar)
+        validate_emission_schedule(&segments, halving_interval)?;
+
+        // 3. Ab tak ka accrual settle karo (pehle schedule khali tha to kuch emit nahi hoga)
+        let now = Clock::get()?.unix_timestamp;
+        config.accrue_rewards(now)?;
+
+        config.reward_mint = ctx.accounts.reward_mint.key();
+        config.halving_interval = halving_interval;
+        config.emission_segments = segments;
+
+        emit!(EmissionScheduleSet {
+            reward_mint: config.reward_mint,
+            segments: config.emission_segments.clone(),
+            halving_interval,
+        });
+
+        msg!(
+            "Rewards initialized. Mint: {}, Segments: {}, Halving: {}s",
+            config.reward_mint,
+            config.emission_segments.len(),
+            halving_interval
+        );
+        Ok(())
+    }
+
     // Deposit Function: User apne tokens stake (jama) karne ke liye call karega.
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> anchor_lang::Result<()> {
         // 1. Check karo ki amount 0 se jyada honi chahiye.
         require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(!ctx.accounts.config.deprecated, ErrorCode::PoolDeprecated); // Purane pool me naya paisa nahi
 
         let staker = &mut ctx.accounts.staker;
         let stake_info = &mut ctx.accounts.stake_info;
+        let config = &mut ctx.accounts.config;
+
+        // Blocked wallet stake nahi kar sakta
+        check_not_blocked(&ctx.accounts.staker_blocklist)?;
+
+        // Allowlist pool hai to staker ka entry hona chahiye
+        check_allowlist(config, ctx.accounts.allowlist_entry.as_ref())?;
 
         // 2. Token Transfer Logic (User -> Vault)
         // Ye instruction banata hai ki user ke account se vault me paise bhejo.
-        let transfer_instruction = token::Transfer {
+        let transfer_instruction = token_interface::TransferChecked {
             from: ctx.accounts.staker_token_account.to_account_info(), // Kahan se nikale (User)
+            mint: ctx.accounts.mint.to_account_info(),                 // Kaunsa token (transfer_checked ke liye)
             to: ctx.accounts.vault.to_account_info(),                  // Kahan dale (Vault)
             authority: staker.to_account_info(),                       // Permission kiski (User)
         };
@@ -71,258 +165,4714 @@ pub mod staking_contract {
             transfer_instruction,
         );
 
-        // Asli transfer yahan execute hota hai using Anchor's token helper.
-        token::transfer(cpi_ctx, amount)?;
+        // Asli transfer yahan execute hota hai (Token aur Token-2022 dono chalega).
+        // Transfer fee wale mint me vault ko `amount` se kam milta hai, isliye jo sach me aaya wahi credit hoga.
+        let vault_before = ctx.accounts.vault.amount;
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+        let amount = received_by(&mut ctx.accounts.vault, vault_before)?;
 
         // 3. Update User Record (User ka khata update karo)
         // Stake info account me likho ki kitna jama kiya aur kab kiya.
-        stake_info.amount += amount; // Amount add kar diya
-        stake_info.deposit_ts = Clock::get()?.unix_timestamp; // Abhi ka time store kiya
+        record_deposit(config, stake_info, amount, staker.key(), true, ctx.accounts.boost_account.as_ref())?;
+
+        // Referrer sirf ek baar set hota hai (pehle deposit pe), baad me ignore
+        if let Some(referral_account) = &ctx.accounts.referral_account {
+            if stake_info.referrer == Pubkey::default() && referral_account.referrer != staker.key() {
+                stake_info.referrer = referral_account.referrer;
+                emit!(ReferrerSet {
+                    staker: staker.key(),
+                    referrer: referral_account.referrer,
+                });
+            }
+        }
 
         // 4. Emit Event (Log generate karo taaki frontend ko pata chale)
         emit!(TokensStaked {
             staker: staker.key(),
             amount,
             total_staked: stake_info.amount,
+            pool: config.stats(),
         });
 
         msg!("Staked {} tokens successfully. Total: {}", amount, stake_info.amount);
         Ok(())
     }
 
-    // Withdraw Function: User apne tokens wapis nikalne ke liye call karega.
-    pub fn withdraw(ctx: Context<Withdraw>) -> anchor_lang::Result<()> {
+    // Deposit For Function: Payer apne tokens se kisi aur (beneficiary) ke naam pe stake karta hai.
+    // Position beneficiary ki hoti hai, payer ka us pe koi haq nahi rehta (payroll / vesting tools ke liye).
+    pub fn deposit_for(ctx: Context<DepositFor>, beneficiary: Pubkey, amount: u64) -> anchor_lang::Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(!ctx.accounts.config.deprecated, ErrorCode::PoolDeprecated);
+
         let stake_info = &mut ctx.accounts.stake_info;
-        let staker = &mut ctx.accounts.staker;
+        let config = &mut ctx.accounts.config;
 
-        // 1. Check Balance (Khate me paisa hai bhi ya nahi?)
-        // Agar balance 0 hai to error feko.
-        require!(stake_info.amount > 0, ErrorCode::InvalidWithdraw);
+        // Na payer blocked ho, na beneficiary
+        check_not_blocked(&ctx.accounts.payer_blocklist)?;
+        check_not_blocked(&ctx.accounts.beneficiary_blocklist)?;
 
-        let total_amount = stake_info.amount; // Sara paisa nikalna hai
+        // Allowlist pool me position ka owner (beneficiary) allowlisted hona chahiye
+        check_allowlist(config, ctx.accounts.allowlist_entry.as_ref())?;
 
-        // Dynamic Fee Calculation
-        // Config se current fee rate padho
-        let fee_bps = ctx.accounts.config.withdraw_fee_bps;
-        let fee_amount = (total_amount * fee_bps) / 10000;
-        let user_amount = total_amount - fee_amount;
+        // 1. Payer -> Vault (payer sign karega). Transfer fee kat ke jo aaya wahi credit hoga.
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.payer_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        );
+        let vault_before = ctx.accounts.vault.amount;
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+        let amount = received_by(&mut ctx.accounts.vault, vault_before)?;
 
-        let bump = ctx.bumps.vault;                 // PDA ka bump seed
-        let mint_key = ctx.accounts.mint.key();     // Token ka mint address
+        // 2. Beneficiary ka khata update karo (boost beneficiary ki holding pe check hota hai).
+        // Third party ka deposit beneficiary ki loyalty reset nahi karta.
+        record_deposit(config, stake_info, amount, beneficiary, false, ctx.accounts.boost_account.as_ref())?;
 
-        // PDA Seeds for Signing (Vault khud sign karega)
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            b"vault",
-            mint_key.as_ref(),
-            &[bump]
-        ]];
+        // 3. Dono parties event me
+        emit!(TokensStakedFor {
+            payer: ctx.accounts.payer.key(),
+            beneficiary,
+            amount,
+            total_staked: stake_info.amount,
+            pool: config.stats(),
+        });
 
-        // 2a. Transfer Fee (Vault -> Fee Vault)
-        if fee_amount > 0 {
-            let fee_transfer_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.fee_vault.to_account_info(),
-                    authority: ctx.accounts.vault.to_account_info(),
-                },
-                signer_seeds
-            );
-            token::transfer(fee_transfer_ctx, fee_amount)?;
-        }
+        msg!("Staked {} tokens for {}. Total: {}", amount, beneficiary, stake_info.amount);
+        Ok(())
+    }
+
+    // Withdraw Function: User apne tokens (pure ya kuch hissa) wapis nikalne ke liye call karega.
+    pub fn withdraw<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>, amount: u64) -> anchor_lang::Result<()> {
+        process_withdraw(ctx, amount, true)
+    }
+
+    // Emergency Withdraw: Pura principal bina reward settle kiye nikalta hai (fee wahi lagti hai).
+    // Agar reward math kabhi fail ho jaye to bhi principal vault me atakna nahi chahiye.
+    // Last settle ke baad ka reward chhoot jata hai; pehle se settle hua pending_rewards baad me claim ho sakta hai.
+    pub fn emergency_withdraw<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>) -> anchor_lang::Result<()> {
+        let amount = ctx.accounts.stake_info.amount;
+        msg!("Emergency withdraw: rewards since the last settle are forfeited");
+        process_withdraw(ctx, amount, false)
+    }
+
+    // Claim Rewards Function: User apna pending reward reward vault se nikalta hai.
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+        stake_info.require_not_tokenized()?;
+        check_not_blocked(&ctx.accounts.staker_blocklist)?;
+        check_not_blocked(&ctx.accounts.recipient_blocklist)?;
+
+        // 1. Latest accrual settle karo (aur boost dobara verify)
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        refresh_boost(config, stake_info, ctx.accounts.staker.key(), ctx.accounts.boost_account.as_ref())?;
+
+        let reward = stake_info.pending_rewards;
+        require!(reward > 0, ErrorCode::NoRewardsToClaim);
+        require!(
+            ctx.accounts.reward_vault.amount >= reward,
+            ErrorCode::RewardVaultUnderfunded
+        );
 
-        // 2b. Transfer Remaining Tokens (Vault -> User)
-        let user_transfer_ctx = CpiContext::new_with_signer(
+        // 2. Reward Vault PDA sign karke recipient ko bhejega
+        transfer_from_reward_vault(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.staker_token_account.to_account_info(),
-                authority: ctx.accounts.vault.to_account_info(),
-            },
-            signer_seeds // Ye seeds proof hain ki program hi owner hai
+            ctx.accounts.reward_vault.to_account_info(),
+            ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.reward_mint,
+            ctx.bumps.reward_vault,
+            reward,
+        )?;
+
+        // 3. Pending reward clear karo
+        stake_info.pending_rewards = 0;
+        config.record_rewards_paid(reward)?;
+
+        emit!(RewardsClaimed {
+            staker: ctx.accounts.staker.key(),
+            recipient: ctx.accounts.recipient.key(),
+            amount: reward,
+        });
+
+        msg!("Claimed {} reward tokens", reward);
+        Ok(())
+    }
+
+    // Compound Function: Jab reward mint aur staked mint same ho, to pending reward
+    // seedha stake me jud jata hai (reward vault -> staking vault), user ke wallet me nahi aata.
+    pub fn compound(ctx: Context<Compound>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+        stake_info.require_not_tokenized()?;
+        check_not_blocked(&ctx.accounts.staker_blocklist)?;
+
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        refresh_boost(config, stake_info, ctx.accounts.staker.key(), ctx.accounts.boost_account.as_ref())?;
+        let old_weight = stake_info.weight()?;
+
+        let reward = stake_info.pending_rewards;
+        require!(reward > 0, ErrorCode::NoRewardsToClaim);
+        require!(
+            ctx.accounts.reward_vault.amount >= reward,
+            ErrorCode::RewardVaultUnderfunded
         );
-        token::transfer(user_transfer_ctx, user_amount)?;
 
-        // 3. Reset User Ledger (User ka khata nil karo)
-        stake_info.amount = 0;
+        // Reward Vault -> Staking Vault (transfer fee ho to vault me jo aaya wahi principal banega)
+        let vault_before = ctx.accounts.vault.amount;
+        transfer_from_reward_vault(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.reward_vault.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.mint,
+            ctx.bumps.reward_vault,
+            reward,
+        )?;
+        let received = received_by(&mut ctx.accounts.vault, vault_before)?;
+        check_stake_caps(config, stake_info.amount, received)?;
 
-        // 4. Emit Event (Log)
-        emit!(TokensWithdrawn {
-            staker: staker.key(),
-            amount: user_amount,
-            fee: fee_amount,
-            total_staked: 0,
+        // Reward ab principal ban gaya
+        stake_info.pending_rewards = 0;
+        config.record_rewards_paid(reward)?;
+        config.add_stake(stake_info.amount, received)?;
+        stake_info.amount = checked_add(stake_info.amount, received)?;
+        commit_weight(config, stake_info, old_weight)?;
+
+        emit!(RewardsCompounded {
+            staker: ctx.accounts.staker.key(),
+            cranker: ctx.accounts.staker.key(),
+            amount: received, // Stake me utna hi juda jitna vault me aaya
+            bounty: 0,
+            total_staked: stake_info.amount,
         });
 
-        msg!("Withdrawn {} tokens. Fee deducted: {}. Total Reset.", user_amount, fee_amount);
+        msg!("Compounded {} reward tokens. Total: {}", received, stake_info.amount);
         Ok(())
     }
-}
 
-// ----------------- ERRORS -----------------
-// Custom Errors jo humne banaye hain
-#[error_code]
-pub enum ErrorCode {
-    #[msg("You are not authorized to perform this action.")]
-    Unauthorized, // Agar koi galat banda admin function call kare
-    #[msg("Amount must be greater than zero.")]
-    InvalidAmount, // Agar 0 ya negative deposit karne ki koshish kare
-    #[msg("No tokens to withdraw.")]
-    InvalidWithdraw, // Agar khali account se withdraw kare
-}
+    // Set Auto Compound: User opt-in/opt-out karta hai ki koi bhi crank uske liye compound kar sake.
+    pub fn set_auto_compound(ctx: Context<SetAutoCompound>, enabled: bool) -> anchor_lang::Result<()> {
+        let stake_info = &mut ctx.accounts.stake_info;
+        stake_info.auto_compound = enabled;
 
-// ----------------- STRUCTS (Data Validation) -----------------
+        emit!(AutoCompoundUpdated {
+            staker: ctx.accounts.staker.key(),
+            enabled,
+        });
 
-// Initialize ke liye validation logic
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,                               // Naya account banao
-        payer = payer,                      // Paise 'payer' dega is account ko banane ke
-        seeds = [b"vault", mint.key().as_ref()], // Ye account ek PDA hai (Address deterministic hai)
-        bump,                               // Bump seed collision bachane ke liye
-        token::mint = mint,                 // Ye account kis token ko hold karega
-        token::authority = vault,           // Iska owner ye khud (Vault PDA) hoga
-    )]
-    pub vault: Account<'info, TokenAccount>, // Ye wo account hai jahan sabka paisa store hoga
+        msg!("Auto compound set to {}", enabled);
+        Ok(())
+    }
 
-    pub mint: Account<'info, Mint>,          // Token ka main address (e.g., USDC ka address)
+    // Update Compound Bounty: Admin crank bounty (bps) set karta hai. Max MAX_COMPOUND_BOUNTY_BPS.
+    pub fn update_compound_bounty(ctx: Context<UpdateFee>, new_bounty_bps: u64) -> anchor_lang::Result<()> {
+        require!(new_bounty_bps <= MAX_COMPOUND_BOUNTY_BPS, ErrorCode::InvalidBountyBps);
 
-    #[account(mut)]
-    pub payer: Signer<'info>,                // Jo fees pay karega (Admin)
+        let config = &mut ctx.accounts.config;
+        let old_bounty = config.compound_bounty_bps;
+        config.compound_bounty_bps = new_bounty_bps;
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + 32 + 8, // Discriminator + Pubkey + u64
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, GlobalConfig>, // Global Config Account
+        emit!(CompoundBountyUpdated {
+            old_bounty,
+            new_bounty: new_bounty_bps
+        });
 
-    pub system_program: Program<'info, System>, // Solana system program (account creation ke liye zaroori)
-    pub token_program: Program<'info, Token>,   // SPL Token program (token transfer ke liye zaroori)
-    pub rent: Sysvar<'info, Rent>,              // Rent sysvar (rent calculation ke liye)
-}
+        msg!("Compound bounty updated from {} to {}", old_bounty, new_bounty_bps);
+        Ok(())
+    }
 
-// Update Fee Validation
-#[derive(Accounts)]
-pub struct UpdateFee<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump,
-        has_one = admin, // Confirm karo ki signer hi admin hai
-    )]
-    pub config: Account<'info, GlobalConfig>,
+    // Tokenize Position: Position ko ek 1-of-1 NFT se represent karo. Iske baad jo bhi
+    // NFT hold karta hai wahi position ka owner hai (marketplace / wallet me dikh sakta hai).
+    // Client ise deposit ke saath same transaction me bhej sakta hai.
+    pub fn tokenize_position(ctx: Context<TokenizePosition>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+        require!(stake_info.amount > 0, ErrorCode::InvalidWithdraw);
+        stake_info.require_not_tokenized()?;
+        // Blocked owner NFT bana ke kisi clean wallet ko nahi de sakta
+        check_not_blocked(&ctx.accounts.owner_blocklist)?;
 
-    pub admin: Signer<'info>, // Sirf admin hi call kar sakta hai
-}
+        // Boost escrow is wallet ke ledger se bandha hai, NFT holder ke saath nahi ja sakta: pehle unregister_boost
+        require_keys_eq!(stake_info.boost_account, Pubkey::default(), ErrorCode::BoostStillEscrowed);
 
-// Deposit ke liye validation logic
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(mut)]
-    pub staker: Signer<'info>, // Jo banda deposit kar raha hai (sign karega)
+        let stake_info_key = stake_info.key();
+        let nonce = config.positions_tokenized.to_le_bytes();
+        config.positions_tokenized = checked_add(config.positions_tokenized, 1)?;
+        let bump = ctx.bumps.position_mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"position_mint",
+            stake_info_key.as_ref(),
+            &nonce,
+            &[bump]
+        ]];
 
-    #[account(
-        mut,
-        seeds = [b"vault", mint.key().as_ref()], // Wahi vault dhoondo jo initialize hua tha
-        bump,
-    )]
-    pub vault: Account<'info, TokenAccount>,
+        // 1. Owner ke wallet me 1 NFT mint karo (position mint PDA khud authority hai)
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.position_mint.to_account_info(),
+                    to: ctx.accounts.owner_nft_account.to_account_info(),
+                    authority: ctx.accounts.position_mint.to_account_info(),
+                },
+                signer_seeds
+            ),
+            1,
+        )?;
 
-    #[account(
-        init_if_needed,                     // Agar user pehli baar aaya hai to account banao check karke
-        payer = staker,                     // Fees staker dega
-        space = 8 + 8 + 8,                  // Kitni jagah chahiye RAM me (Discriminator + u64 + i64)
-        seeds = [b"user", staker.key().as_ref()], // Har user ka alag PDA hoga uske wallet address ke base pe
-        bump
-    )]
-    pub stake_info: Account<'info, UserStakeInfo>, // Ye user ka personal ledger hai
+        // 2. Mint authority hata do taaki supply hamesha 1 rahe
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.position_mint.to_account_info(),
+                    account_or_mint: ctx.accounts.position_mint.to_account_info(),
+                },
+                signer_seeds
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
 
-    pub mint: Account<'info, Mint>, // Token Mint
+        stake_info.position_mint = ctx.accounts.position_mint.key();
 
-    #[account(mut)]
-    pub staker_token_account: Account<'info, TokenAccount>, // User ka token wallet jahan se paise katenge
+        emit!(PositionTokenized {
+            owner: ctx.accounts.owner.key(),
+            stake_info: stake_info_key,
+            position_mint: stake_info.position_mint,
+            amount: stake_info.amount,
+        });
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+        msg!("Position tokenized. NFT mint: {}", stake_info.position_mint);
+        Ok(())
+    }
 
-// Withdraw ke liye validation logic
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(mut)]
-    pub staker: Signer<'info>, // Jo banda withdraw maang raha hai
+    // Withdraw NFT Position: NFT holder NFT dikha ke (aur burn karke) poori position nikalta hai.
+    // Principal (fee katke) recipient ko jata hai aur pending rewards reward recipient ko.
+    pub fn withdraw_nft_position<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawNftPosition<'info>>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+        require!(stake_info.amount > 0, ErrorCode::InvalidWithdraw);
+        check_not_blocked(&ctx.accounts.holder_blocklist)?;
+        check_not_blocked(&ctx.accounts.recipient_blocklist)?;
+        if let Some(reward_recipient) = &ctx.accounts.reward_recipient {
+            let entry = ctx.accounts.reward_recipient_blocklist.as_ref().ok_or(error!(ErrorCode::BlocklistAccountMismatch))?;
+            check_not_blocked_for(&reward_recipient.owner, entry)?;
+        }
 
-    #[account(
-        mut,
-        seeds = [b"vault", mint.key().as_ref()],
-        bump,
-    )]
-    pub vault: Account<'info, TokenAccount>, // Vault se paise nikalenge
+        // 1. Rewards settle karo
+        let now = Clock::get()?.unix_timestamp;
+        config.accrue_rewards(now)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        let old_weight = stake_info.weight()?;
 
-    #[account(
-        mut, // Modify karenge kyunki balance 0 karna hai
-        seeds = [b"user", staker.key().as_ref()],
-        bump
-    )]
-    pub stake_info: Account<'info, UserStakeInfo>, // User ka ledger check karenge
+        // 2. NFT burn (holder ka signature chahiye)
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.position_mint.to_account_info(),
+                    from: ctx.accounts.holder_nft_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
 
-    pub mint: Account<'info, Mint>,
+        // 3. Principal payout (normal withdraw jaisa fee, loyalty discount ke saath)
+        let total_amount = stake_info.amount;
+        config.consume_withdraw_budget(total_amount, now)?;
+        let fee_discount_bps = config.loyalty_discount_bps(stake_info, now);
+        let (fee_amount, user_amount) = config.discounted_withdraw_fee(total_amount, fee_discount_bps)?;
 
-    #[account(mut)]
-    pub staker_token_account: Account<'info, TokenAccount>, // Jahan paisa wapis aayega user ke paas
+        let referral_fee = credit_referral(
+            config,
+            stake_info,
+            ctx.accounts.referral_account.as_mut(),
+            ctx.accounts.holder.key(),
+            fee_amount,
+        )?;
+        let insurance_fee = config.insurance_share(fee_amount)?;
+        let admin_fee = checked_sub(checked_sub(fee_amount, referral_fee)?, insurance_fee)?;
+        send_to_insurance(
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.vault,
+            ctx.accounts.insurance_vault.as_ref(),
+            &ctx.accounts.mint,
+            ctx.bumps.vault,
+            insurance_fee,
+        )?;
 
-    #[account(mut)]
-    pub fee_vault: Account<'info, TokenAccount>, // Admin ka account jahan fee jayegi
+        pay_admin_fee(
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.vault,
+            &ctx.accounts.fee_vault,
+            &config.fee_recipients,
+            ctx.remaining_accounts,
+            &ctx.accounts.mint,
+            ctx.bumps.vault,
+            admin_fee,
+        )?;
+        transfer_from_vault(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.mint,
+            ctx.bumps.vault,
+            user_amount,
+        )?;
 
-    #[account(
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, GlobalConfig>, // Fee rate janne ke liye config chahiye
+        // 4. Pending rewards bhi holder ke hain, yahin pay karo
+        let reward = stake_info.pending_rewards;
+        if reward > 0 {
+            let (Some(reward_vault), Some(reward_recipient), Some(reward_mint)) =
+                (&ctx.accounts.reward_vault, &ctx.accounts.reward_recipient, &ctx.accounts.reward_mint)
+            else {
+                return err!(ErrorCode::RewardAccountsMissing);
+            };
+            require!(reward_vault.amount >= reward, ErrorCode::RewardVaultUnderfunded);
+            transfer_from_reward_vault(
+                ctx.accounts.token_program.to_account_info(),
+                reward_vault.to_account_info(),
+                reward_recipient.to_account_info(),
+                reward_mint,
+                ctx.bumps.reward_vault,
+                reward,
+            )?;
+        }
 
-    pub token_program: Program<'info, Token>,
-}
+        // 5. Ledger reset, NFT link hata do
+        stake_info.amount = 0;
+        stake_info.pending_rewards = 0;
+        config.record_rewards_paid(reward)?;
+        stake_info.position_mint = Pubkey::default();
+        commit_weight(config, stake_info, old_weight)?;
+        config.remove_stake(0, total_amount)?;
+        config.record_withdrawal(total_amount, fee_amount)?;
 
+        emit!(NftPositionWithdrawn {
+            holder: ctx.accounts.holder.key(),
+            position_mint: ctx.accounts.position_mint.key(),
+            recipient: ctx.accounts.recipient.key(),
+            amount: user_amount,
+            fee: fee_amount,
+            fee_discount_bps,
+            rewards: reward,
+            pool: config.stats(),
+        });
 
-// ----------------- DATA ACCOUNTS -----------------
-// On-chain data storage structure specific to our program
+        msg!("NFT position withdrawn: {} tokens, fee {}, rewards {}", user_amount, fee_amount, reward);
+        Ok(())
+    }
 
-#[account]
-pub struct UserStakeInfo {
-    pub amount: u64,        // Kitna paisa jama hai (8 bytes)
-    pub deposit_ts: i64,    // Kab jama kiya (Timestamp) (8 bytes)
-}
+    // Set Boost Tiers: Admin partner tokens / NFT mints ki list set karta hai jinhe hold karne pe
+    // reward weight multiplier milta hai. Existing boosts agle accrual update pe re-verify honge.
+    pub fn set_boost_tiers(ctx: Context<UpdateFee>, tiers: Vec<BoostTier>) -> anchor_lang::Result<()> {
+        require!(tiers.len() <= MAX_BOOST_TIERS, ErrorCode::InvalidBoostTier);
+        for tier in tiers.iter() {
+            require!(
+                tier.multiplier_bps >= 10000 && tier.multiplier_bps <= MAX_BOOST_MULTIPLIER_BPS,
+                ErrorCode::InvalidBoostTier
+            );
+            require!(tier.min_balance > 0, ErrorCode::InvalidBoostTier);
+        }
 
-#[account]
-pub struct GlobalConfig {
-    pub admin: Pubkey,       // Admin kaun hai
-    pub withdraw_fee_bps: u64, // Current Fee (Basis Points)
-}
+        let config = &mut ctx.accounts.config;
+        config.boost_tiers = tiers;
 
-// ----------------- EVENTS -----------------
-// Ye logs hain jo frontend catch kar sakta hai bina chain state padhe
+        emit!(BoostTiersUpdated {
+            tiers: config.boost_tiers.clone(),
+        });
 
-#[event]
-pub struct TokensStaked {
-    pub staker: Pubkey,
-    pub amount: u64,
-    pub total_staked: u64,
-}
+        msg!("Boost tiers updated: {} tiers", config.boost_tiers.len());
+        Ok(())
+    }
 
-#[event]
-pub struct TokensWithdrawn {
-    pub staker: Pubkey,
-    pub amount: u64,
-    pub fee: u64,
-    pub total_staked: u64,
-}
+    // Set Loyalty Tiers: Purane / bade stakers ko withdraw fee me discount. Tier tab lagta hai jab
+    // position last deposit se kam se kam `min_duration` seconds purani ho aur `min_amount` ki ho.
+    // Kai tier match karein to sabse bada discount lagta hai. Empty list = discount band.
+    pub fn set_loyalty_tiers(ctx: Context<UpdateFee>, tiers: Vec<LoyaltyTier>) -> anchor_lang::Result<()> {
+        require!(tiers.len() <= MAX_LOYALTY_TIERS, ErrorCode::InvalidLoyaltyTier);
+        for tier in tiers.iter() {
+            require!(
+                tier.discount_bps > 0 && tier.discount_bps <= 10000 && tier.min_duration >= 0,
+                ErrorCode::InvalidLoyaltyTier
+            );
+        }
 
-#[event]
-pub struct FeeUpdated {
-    pub old_fee: u64,
-    pub new_fee: u64,
+        let config = &mut ctx.accounts.config;
+        config.loyalty_tiers = tiers;
+
+        emit!(LoyaltyTiersUpdated {
+            tiers: config.loyalty_tiers.clone(),
+        });
+
+        msg!("Loyalty tiers updated: {} tiers", config.loyalty_tiers.len());
+        Ok(())
+    }
+
+    // Set Fee Split: Admin withdraw fee ka apna hissa kai wallets me baant sakta hai (weights ka total 10000).
+    // Empty list = pehle jaisa, poora hissa fee_vault me. Withdraw me recipients ke token accounts
+    // isi order me remaining_accounts me dene hote hain.
+    // FeeRecipient aur validation token_contract ke transfer tax split wale hi hain.
+    pub fn set_fee_split(ctx: Context<UpdateFee>, recipients: Vec<FeeRecipient>) -> anchor_lang::Result<()> {
+        require!(token_contract::is_valid_fee_split(&recipients), ErrorCode::InvalidFeeSplit);
+
+        let config = &mut ctx.accounts.config;
+        config.fee_recipients = recipients;
+
+        emit!(FeeSplitUpdated {
+            recipients: config.fee_recipients.clone(),
+        });
+
+        msg!("Fee split updated: {} recipients", config.fee_recipients.len());
+        Ok(())
+    }
+
+    // Register Boost: Staker tier ke `min_balance` partner tokens / NFT boost escrow (`b"boost_escrow" + stake_info`)
+    // me lock karta hai. Tokens unregister_boost tak escrow me rehte hain, isliye udhaar (flash loan) liye
+    // tokens se boost le ke settle karwana kaam nahi karta. Collection tier me NFT ka verified Metaplex
+    // collection tier ke `mint` se match hona chahiye (metadata account `boost_metadata` me).
+    pub fn register_boost(ctx: Context<RegisterBoost>, tier_index: u8) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+        stake_info.require_not_tokenized()?;
+
+        // 1. Tier exist karta hai aur token us tier ka hai?
+        let tier = *config
+            .boost_tiers
+            .get(tier_index as usize)
+            .ok_or(ErrorCode::InvalidBoostTier)?;
+        let boost_mint = ctx.accounts.boost_mint.key();
+        let collection = if tier.collection {
+            let metadata = ctx.accounts.boost_metadata.as_ref().ok_or(ErrorCode::BoostRequirementNotMet)?;
+            let collection = verified_collection(metadata, &boost_mint).ok_or(ErrorCode::BoostRequirementNotMet)?;
+            require_keys_eq!(collection, tier.mint, ErrorCode::BoostRequirementNotMet);
+            collection
+        } else {
+            require_keys_eq!(boost_mint, tier.mint, ErrorCode::BoostRequirementNotMet);
+            Pubkey::default()
+        };
+        require!(
+            ctx.accounts.staker_boost_account.amount >= tier.min_balance,
+            ErrorCode::BoostRequirementNotMet
+        );
+
+        // 2. Purane weight pe reward settle karo
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        let old_weight = stake_info.weight()?;
+
+        // 3. min_balance tokens escrow me lock karo. Transfer fee wale mint me jo pahuncha wahi gina jata hai.
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.staker_boost_account.to_account_info(),
+                mint: ctx.accounts.boost_mint.to_account_info(),
+                to: ctx.accounts.boost_escrow.to_account_info(),
+                authority: ctx.accounts.staker.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(transfer_ctx, tier.min_balance, ctx.accounts.boost_mint.decimals)?;
+        ctx.accounts.boost_escrow.reload()?;
+        require!(ctx.accounts.boost_escrow.amount >= tier.min_balance, ErrorCode::BoostRequirementNotMet);
+
+        // 4. Boost lagao
+        let boost_escrow = ctx.accounts.boost_escrow.key();
+        stake_info.boost_account = boost_escrow;
+        stake_info.boost_collection = collection;
+        stake_info.boost_tier = tier_index;
+        stake_info.boost_bps = tier.multiplier_bps;
+        commit_weight(config, stake_info, old_weight)?;
+
+        emit!(BoostRegistered {
+            owner: ctx.accounts.staker.key(),
+            boost_account: boost_escrow,
+            tier: tier_index,
+            multiplier_bps: tier.multiplier_bps,
+        });
+
+        msg!("Boost registered: tier {} ({} bps), {} tokens escrowed", tier_index, tier.multiplier_bps, tier.min_balance);
+        Ok(())
+    }
+
+    // Unregister Boost: Boost hatao aur escrow ke saare tokens staker ko wapis (escrow band, rent bhi wapis).
+    // Tier hatne / badalne se boost pehle hi 0 ho gaya ho tab bhi isi se tokens wapis milte hain.
+    pub fn unregister_boost(ctx: Context<UnregisterBoost>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+        let boost_escrow = ctx.accounts.boost_escrow.key();
+        require_keys_eq!(stake_info.boost_account, boost_escrow, ErrorCode::BoostAccountMismatch);
+
+        // 1. Boosted weight pe settle karo, phir boost hatao (escrow ab tak locked tha)
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        let old_weight = stake_info.weight()?;
+        stake_info.clear_boost();
+        commit_weight(config, stake_info, old_weight)?;
+
+        // 2. Escrow khaali karke band karo. Escrow PDA apne seeds se sign karta hai.
+        let stake_info_key = stake_info.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"boost_escrow",
+            stake_info_key.as_ref(),
+            &[ctx.bumps.boost_escrow]
+        ]];
+        let escrowed = ctx.accounts.boost_escrow.amount;
+        if escrowed > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.boost_escrow.to_account_info(),
+                    mint: ctx.accounts.boost_mint.to_account_info(),
+                    to: ctx.accounts.staker_boost_account.to_account_info(),
+                    authority: ctx.accounts.boost_escrow.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token_interface::transfer_checked(transfer_ctx, escrowed, ctx.accounts.boost_mint.decimals)?;
+        }
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.boost_escrow.to_account_info(),
+                destination: ctx.accounts.staker.to_account_info(),
+                authority: ctx.accounts.boost_escrow.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        emit!(BoostRemoved {
+            owner: ctx.accounts.staker.key(),
+            boost_account: boost_escrow,
+        });
+
+        msg!("Boost removed, {} tokens returned", escrowed);
+        Ok(())
+    }
+
+    // Register Referrer: Koi bhi wallet apna referral account banata hai. Iske baad naye stakers
+    // deposit me ye account de kar isko referrer bana sakte hain.
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> anchor_lang::Result<()> {
+        let referral_account = &mut ctx.accounts.referral_account;
+        referral_account.referrer = ctx.accounts.referrer.key();
+        referral_account.bump = ctx.bumps.referral_account;
+
+        emit!(ReferrerRegistered {
+            referrer: referral_account.referrer,
+        });
+
+        msg!("Referrer registered: {}", referral_account.referrer);
+        Ok(())
+    }
+
+    // Update Referral Share: Admin withdraw fee ka kitna hissa (bps) referrer ko jayega, set karta hai.
+    pub fn update_referral_share(ctx: Context<UpdateFee>, new_share_bps: u64) -> anchor_lang::Result<()> {
+        // Referral + insurance milake fee ke 100% se jyada nahi ho sakte
+        require!(
+            new_share_bps.saturating_add(ctx.accounts.config.insurance_fee_share_bps) <= 10000,
+            ErrorCode::InvalidReferralShare
+        );
+
+        let config = &mut ctx.accounts.config;
+        let old_share = config.referral_fee_share_bps;
+        config.referral_fee_share_bps = new_share_bps;
+
+        emit!(ReferralShareUpdated {
+            old_share,
+            new_share: new_share_bps
+        });
+
+        msg!("Referral share updated from {} to {}", old_share, new_share_bps);
+        Ok(())
+    }
+
+    // Claim Referral Rewards: Referrer apna jama hua fee share staking vault se nikalta hai.
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> anchor_lang::Result<()> {
+        check_not_blocked(&ctx.accounts.referrer_blocklist)?;
+        check_not_blocked(&ctx.accounts.recipient_blocklist)?;
+        let referral_account = &mut ctx.accounts.referral_account;
+        let amount = referral_account.claimable;
+        require!(amount > 0, ErrorCode::NoRewardsToClaim);
+
+        transfer_from_vault(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.mint,
+            ctx.bumps.vault,
+            amount,
+        )?;
+
+        referral_account.claimable = 0;
+        ctx.accounts.config.referral_owed = checked_sub(ctx.accounts.config.referral_owed, amount)?;
+
+        emit!(ReferralRewardsClaimed {
+            referrer: ctx.accounts.referrer.key(),
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+        });
+
+        msg!("Claimed {} referral tokens", amount);
+        Ok(())
+    }
+
+    // Update Withdraw Limit: Bank-run se bachne ke liye har `window_secs` ki window me vault se kitna
+    // principal nikal sakta hai. `max_amount` absolute limit, `max_bps` window shuru hone ke TVL ka hissa.
+    // Dono set hon to jo chhota ho wo lagta hai. window_secs = 0 = limit band.
+    pub fn update_withdraw_limit(
+        ctx: Context<UpdateFee>,
+        window_secs: i64,
+        max_amount: u64,
+        max_bps: u64,
+    ) -> anchor_lang::Result<()> {
+        require!(window_secs >= 0 && max_bps <= 10000, ErrorCode::InvalidWithdrawLimit);
+        require!(window_secs == 0 || max_amount > 0 || max_bps > 0, ErrorCode::InvalidWithdrawLimit);
+
+        let config = &mut ctx.accounts.config;
+        config.withdraw_window_secs = window_secs;
+        config.withdraw_window_max_amount = max_amount;
+        config.withdraw_window_max_bps = max_bps;
+        // Nayi settings naye window se lagengi
+        config.withdraw_window_start = 0;
+        config.withdraw_window_used = 0;
+
+        emit!(WithdrawLimitUpdated {
+            window_secs,
+            max_amount,
+            max_bps,
+        });
+
+        msg!("Withdraw limit updated. Window: {}s, Max: {}, Max bps: {}", window_secs, max_amount, max_bps);
+        Ok(())
+    }
+
+    // Update Deposit Caps: Admin pool aur per-user limit set karta hai (0 = koi limit nahi).
+    // Capped launch campaigns aur early pools ka risk limit karne ke liye.
+    pub fn update_deposit_caps(
+        ctx: Context<UpdateFee>,
+        max_total_staked: u64,
+        max_per_user: u64,
+    ) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.max_total_staked = max_total_staked;
+        config.max_per_user = max_per_user;
+
+        emit!(DepositCapsUpdated {
+            max_total_staked,
+            max_per_user,
+        });
+
+        msg!("Deposit caps updated. Pool: {}, Per user: {}", max_total_staked, max_per_user);
+        Ok(())
+    }
+
+    // Update Min Position: Admin minimum position size set karta hai (0 = koi minimum nahi).
+    pub fn update_min_position(ctx: Context<UpdateFee>, min_position: u64) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_min = config.min_position;
+        config.min_position = min_position;
+
+        emit!(MinPositionUpdated {
+            old_min,
+            new_min: min_position,
+        });
+
+        msg!("Minimum position updated from {} to {}", old_min, min_position);
+        Ok(())
+    }
+
+    // Set Allowlist Enabled: Admin pool ko sirf whitelisted partners ke liye band/chalu karta hai.
+    pub fn set_allowlist_enabled(ctx: Context<UpdateFee>, enabled: bool) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.allowlist_enabled = enabled;
+
+        emit!(AllowlistToggled { enabled });
+
+        msg!("Allowlist enabled: {}", enabled);
+        Ok(())
+    }
+
+    // Set Allowlist Root: Admin Merkle root update karta hai. Purane root se bani entries invalid ho jati hain.
+    pub fn set_allowlist_root(ctx: Context<UpdateFee>, root: [u8; 32]) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.allowlist_root = root;
+
+        emit!(AllowlistRootUpdated { root });
+
+        msg!("Allowlist root updated");
+        Ok(())
+    }
+
+    // Add Allowlist Entry: Admin kisi wallet ka per-wallet allowlist PDA banata hai.
+    pub fn add_allowlist_entry(ctx: Context<AddAllowlistEntry>, wallet: Pubkey) -> anchor_lang::Result<()> {
+        let entry = &mut ctx.accounts.allowlist_entry;
+        entry.wallet = wallet;
+        entry.merkle_root = [0u8; 32]; // Admin entry kisi root pe depend nahi karti
+        entry.bump = ctx.bumps.allowlist_entry;
+
+        emit!(AllowlistEntryAdded {
+            wallet,
+            by_proof: false,
+        });
+
+        msg!("Allowlist entry added for {}", wallet);
+        Ok(())
+    }
+
+    // Remove Allowlist Entry: Admin entry hata deta hai (rent admin ko wapis).
+    pub fn remove_allowlist_entry(ctx: Context<RemoveAllowlistEntry>) -> anchor_lang::Result<()> {
+        let wallet = ctx.accounts.allowlist_entry.wallet;
+
+        emit!(AllowlistEntryRemoved { wallet });
+
+        msg!("Allowlist entry removed for {}", wallet);
+        Ok(())
+    }
+
+    // Verify Allowlist Proof: Wallet apna Merkle proof dikha ke khud apni entry banata hai.
+    // Isko deposit ke saath same transaction me bheja ja sakta hai.
+    pub fn verify_allowlist_proof(ctx: Context<VerifyAllowlistProof>, proof: Vec<[u8; 32]>) -> anchor_lang::Result<()> {
+        let root = ctx.accounts.config.allowlist_root;
+        require!(root != [0u8; 32], ErrorCode::InvalidMerkleProof);
+
+        let wallet = ctx.accounts.wallet.key();
+        let leaf = keccak::hashv(&[wallet.as_ref()]).0;
+        require!(verify_merkle_proof(&proof, root, leaf), ErrorCode::InvalidMerkleProof);
+
+        let entry = &mut ctx.accounts.allowlist_entry;
+        entry.wallet = wallet;
+        entry.merkle_root = root; // Root badla to ye entry invalid
+        entry.bump = ctx.bumps.allowlist_entry;
+
+        emit!(AllowlistEntryAdded {
+            wallet,
+            by_proof: true,
+        });
+
+        msg!("Allowlist proof verified for {}", wallet);
+        Ok(())
+    }
+
+    // Transfer Position: Owner apni poori position (amount, deposit time, pending rewards)
+    // bina unstake kiye kisi aur wallet ko bech/gift kar sakta hai. Purana ledger close ho jata hai.
+    pub fn transfer_position(ctx: Context<TransferPosition>, new_owner: Pubkey) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // 1. Admin ne transfers band to nahi kiye?
+        require!(!config.position_transfers_paused, ErrorCode::PositionTransfersDisabled);
+        require_keys_neq!(new_owner, ctx.accounts.owner.key(), ErrorCode::InvalidNewOwner);
+        // Blocked wallet position clean wallet ko de ke freeze bypass na kare (aur blocked ko mile bhi nahi)
+        check_not_blocked(&ctx.accounts.owner_blocklist)?;
+        check_not_blocked(&ctx.accounts.new_owner_blocklist)?;
+
+        let from_info = &mut ctx.accounts.from_stake_info;
+        let to_info = &mut ctx.accounts.to_stake_info;
+        require!(from_info.amount > 0, ErrorCode::InvalidWithdraw);
+        // NFT wali position NFT bech ke transfer hoti hai
+        from_info.require_not_tokenized()?;
+        // Purana ledger band hoga, uska boost escrow pehle unregister_boost se wapis lo
+        require_keys_eq!(from_info.boost_account, Pubkey::default(), ErrorCode::BoostStillEscrowed);
+
+        // 2. Naye owner ke paas pehle se active stake nahi hona chahiye (deposit time mix na ho),
+        // aur uska ledger kisi NFT se bandha na ho (warna position NFT holder ki ho jayegi)
+        require!(to_info.amount == 0, ErrorCode::PositionNotEmpty);
+        to_info.require_not_tokenized()?;
+
+        // 3. Dono ledgers ko latest accumulator tak settle karo
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        from_info.settle_rewards(config.acc_reward_per_share)?;
+        to_info.settle_rewards(config.acc_reward_per_share)?;
+        let from_weight = from_info.weight()?;
+        let to_weight = to_info.weight()?;
+
+        // 4. Position move karo. Auto compound ka opt-in naya owner khud karega.
+        // Naye owner ka apna boost (escrow) ho to wo naye amount pe bhi lagta hai.
+        let amount = from_info.amount;
+        let pending = from_info.pending_rewards;
+        to_info.amount = amount;
+        to_info.deposit_ts = from_info.deposit_ts;
+        to_info.loyalty_start_ts = from_info.loyalty_start_ts;
+        to_info.pending_rewards = checked_add(to_info.pending_rewards, pending)?;
+        commit_weight(config, to_info, to_weight)?;
+
+        // Purana ledger `close = owner` se band ho jata hai, bas pool weight se hatao
+        from_info.amount = 0;
+        commit_weight(config, from_info, from_weight)?;
+
+        emit!(PositionTransferred {
+            from: ctx.accounts.owner.key(),
+            to: new_owner,
+            amount,
+            pending_rewards: pending,
+            deposit_ts: to_info.deposit_ts,
+        });
+
+        msg!("Position of {} tokens transferred to {}", amount, new_owner);
+        Ok(())
+    }
+
+    // Pause Position Transfers: Admin transfer_position ko band/chalu kar sakta hai.
+    pub fn set_position_transfers_paused(ctx: Context<UpdateFee>, paused: bool) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.position_transfers_paused = paused;
+
+        emit!(PositionTransfersPaused { paused });
+
+        msg!("Position transfers paused: {}", paused);
+        Ok(())
+    }
+
+    // Set Pool Deprecated: Naya pool version launch hone pe admin migration target (naye pool ka program)
+    // set karta hai. Target set hote hi migrate_position khul jata hai (normal withdraw fee ke saath).
+    // Deprecated pool me naye deposits band, aur migrate_position bina fee ke.
+    pub fn set_pool_deprecated(
+        ctx: Context<UpdateFee>,
+        deprecated: bool,
+        migration_target: Pubkey,
+    ) -> anchor_lang::Result<()> {
+        require!(!deprecated || migration_target != Pubkey::default(), ErrorCode::MigrationTargetMismatch);
+        require_keys_neq!(migration_target, crate::ID, ErrorCode::MigrationTargetMismatch);
+
+        let config = &mut ctx.accounts.config;
+        config.deprecated = deprecated;
+        config.migration_target = migration_target;
+
+        emit!(PoolDeprecated {
+            deprecated,
+            migration_target: config.migration_target,
+        });
+
+        msg!("Pool deprecated: {}, migration target: {}", deprecated, config.migration_target);
+        Ok(())
+    }
+
+    // Migrate Position: Poori position naye pool me. Deprecated pool se fee maaf, live pool se
+    // normal withdraw fee (loyalty discount, referral / insurance share aur fee split ke saath).
+    // Principal vault -> staker ka token account -> (same tx me) naye pool ka `deposit`.
+    // remaining_accounts: pehle fee split ke recipients (sirf live pool me, withdraw jaisa order),
+    // phir naye pool ke Deposit accounts usi order me; staker ka signature CPI tak jata hai.
+    // Pending rewards yahin rehte hain (claim_rewards se nikalo). Is pool me lock terms nahi hain,
+    // isliye carry karne ko kuch nahi; naye pool me deposit_ts migration ke time se shuru hota hai.
+    pub fn migrate_position<'info>(ctx: Context<'_, '_, '_, 'info, MigratePosition<'info>>) -> anchor_lang::Result<()> {
+        // 1. Sirf admin ke bataye naye pool me
+        let target = ctx.accounts.config.migration_target;
+        require!(target != Pubkey::default(), ErrorCode::MigrationTargetMismatch);
+        require_keys_eq!(ctx.accounts.target_program.key(), target, ErrorCode::MigrationTargetMismatch);
+        require!(ctx.accounts.target_program.executable, ErrorCode::MigrationTargetMismatch);
+
+        let stake_info = &mut ctx.accounts.stake_info;
+        stake_info.require_not_tokenized()?;
+        check_not_blocked(&ctx.accounts.staker_blocklist)?;
+
+        // 2. Fee quote (poori position), deprecated pool me fee maaf
+        let now = Clock::get()?.unix_timestamp;
+        let amount = stake_info.amount;
+        let quote = quote_withdrawal(&ctx.accounts.config, stake_info, amount, now)?;
+        let fee_waived = ctx.accounts.config.deprecated;
+        let fee_amount = if fee_waived { 0 } else { quote.fee };
+
+        // 3. Rewards settle karo (withdraw jaisa), pending rewards isi pool me claimable rehte hain
+        let config = &mut ctx.accounts.config;
+        config.accrue_rewards(now)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        refresh_boost(config, stake_info, ctx.accounts.staker.key(), ctx.accounts.boost_account.as_ref())?;
+        let old_weight = stake_info.weight()?;
+
+        // 4. Fee withdraw jaisi hi bantti hai: referrer, insurance, baaki admin (fee split)
+        let fee_accounts = if fee_waived { 0 } else { config.fee_recipients.len() };
+        require!(ctx.remaining_accounts.len() >= fee_accounts, ErrorCode::FeeRecipientsMissing);
+        let (fee_recipient_accounts, target_accounts) = ctx.remaining_accounts.split_at(fee_accounts);
+        if fee_amount > 0 {
+            let referral_fee = credit_referral(
+                config,
+                stake_info,
+                ctx.accounts.referral_account.as_mut(),
+                ctx.accounts.staker.key(),
+                fee_amount,
+            )?;
+            let insurance_fee = quote.insurance_fee;
+            let admin_fee = checked_sub(checked_sub(fee_amount, referral_fee)?, insurance_fee)?;
+            send_to_insurance(
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.vault,
+                ctx.accounts.insurance_vault.as_ref(),
+                &ctx.accounts.mint,
+                ctx.bumps.vault,
+                insurance_fee,
+            )?;
+            pay_admin_fee(
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.vault,
+                &ctx.accounts.fee_vault,
+                &config.fee_recipients,
+                fee_recipient_accounts,
+                &ctx.accounts.mint,
+                ctx.bumps.vault,
+                admin_fee,
+            )?;
+        }
+
+        // 5. Baaki principal staker ke token account me. Rate limit nahi lagta,
+        // kyunki paisa system se bahar nahi, naye pool me ja raha hai.
+        let user_amount = checked_sub(amount, fee_amount)?;
+        let balance_before = ctx.accounts.staker_token_account.amount;
+        transfer_from_vault(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.staker_token_account.to_account_info(),
+            &ctx.accounts.mint,
+            ctx.bumps.vault,
+            user_amount,
+        )?;
+        ctx.accounts.staker_token_account.reload()?;
+        let received = checked_sub(ctx.accounts.staker_token_account.amount, balance_before)?;
+
+        // 6. Ledger band karo
+        stake_info.amount = 0;
+        commit_weight(config, stake_info, old_weight)?;
+        config.remove_stake(0, amount)?;
+        config.record_withdrawal(amount, fee_amount)?;
+
+        // 7. Naye pool ka deposit (same interface wala naya program version)
+        let ix = anchor_lang::solana_program::instruction::Instruction {
+            program_id: target,
+            accounts: target_accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.key(),
+                    is_signer: a.is_signer,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data: anchor_lang::InstructionData::data(&instruction::Deposit { amount: received }),
+        };
+        let mut infos = target_accounts.to_vec();
+        infos.push(ctx.accounts.target_program.to_account_info());
+        anchor_lang::solana_program::program::invoke(&ix, &infos)?;
+
+        // 8. Jo nikla wo poora naye pool me gaya hona chahiye, staker ke account me kuch nahi bacha
+        ctx.accounts.staker_token_account.reload()?;
+        require!(
+            ctx.accounts.staker_token_account.amount == balance_before,
+            ErrorCode::MigrationIncomplete
+        );
+
+        emit!(PositionMigrated {
+            staker: ctx.accounts.staker.key(),
+            to_program: target,
+            amount,
+            deposited: received,
+            fee: fee_amount,
+            fee_waived,
+            pool: ctx.accounts.config.stats(),
+        });
+
+        msg!("Migrated {} tokens to pool {}. Fee: {}", received, target, fee_amount);
+        Ok(())
+    }
+
+    // Crank Compound: Permissionless. Koi bhi (bot) opted-in user ke liye compound kar sakta hai
+    // aur badle me reward ka chhota hissa (compound_bounty_bps) bounty ke roop me leta hai.
+    pub fn crank_compound(ctx: Context<CrankCompound>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+
+        // 1. User ne opt-in kiya hai ya nahi?
+        require!(stake_info.auto_compound, ErrorCode::AutoCompoundDisabled);
+        stake_info.require_not_tokenized()?;
+        // Blocked owner ki position nahi badhegi, aur blocked cranker ko bounty nahi milegi
+        check_not_blocked(&ctx.accounts.owner_blocklist)?;
+        check_not_blocked(&ctx.accounts.cranker_blocklist)?;
+        check_not_blocked(&ctx.accounts.recipient_blocklist)?;
+
+        // Boost account cranker ko dena hoga, warna boost wale user ka crank fail hoga
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        refresh_boost(config, stake_info, ctx.accounts.owner.key(), ctx.accounts.boost_account.as_ref())?;
+        let old_weight = stake_info.weight()?;
+
+        let reward = stake_info.pending_rewards;
+        require!(reward > 0, ErrorCode::NoRewardsToClaim);
+        require!(
+            ctx.accounts.reward_vault.amount >= reward,
+            ErrorCode::RewardVaultUnderfunded
+        );
+
+        // 2. Bounty nikalo, baaki compound hoga
+        let (bounty, compounded) = checked_split_bps(reward, config.compound_bounty_bps)?;
+
+        if bounty > 0 {
+            transfer_from_reward_vault(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.reward_vault.to_account_info(),
+                ctx.accounts.cranker_token_account.to_account_info(),
+                &ctx.accounts.mint,
+                ctx.bumps.reward_vault,
+                bounty,
+            )?;
+        }
+
+        // Transfer fee ho to vault me jo aaya wahi principal banega
+        let mut compounded = compounded;
+        if compounded > 0 {
+            let vault_before = ctx.accounts.vault.amount;
+            transfer_from_reward_vault(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.reward_vault.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                &ctx.accounts.mint,
+                ctx.bumps.reward_vault,
+                compounded,
+            )?;
+            compounded = received_by(&mut ctx.accounts.vault, vault_before)?;
+        }
+        check_stake_caps(config, stake_info.amount, compounded)?;
+
+        // 3. User ka ledger update
+        stake_info.pending_rewards = 0;
+        config.record_rewards_paid(reward)?;
+        config.add_stake(stake_info.amount, compounded)?;
+        stake_info.amount = checked_add(stake_info.amount, compounded)?;
+        commit_weight(config, stake_info, old_weight)?;
+
+        emit!(RewardsCompounded {
+            staker: ctx.accounts.owner.key(),
+            cranker: ctx.accounts.cranker.key(),
+            amount: compounded,
+            bounty,
+            total_staked: stake_info.amount,
+        });
+
+        msg!("Cranked compound of {} tokens (bounty {})", compounded, bounty);
+        Ok(())
+    }
+
+    // Quote Withdraw: Read-only. `withdraw(amount)` kitna fee katega aur kitna milega, wahi checks ke saath
+    // batata hai. Result return data me jata hai (client simulate / `.view()` karke padh sakta hai).
+    // Window budget bhi config ki copy pe check hota hai, taaki quote vahi fail kare jo withdraw karega.
+    pub fn quote_withdraw(ctx: Context<QuoteWithdraw>, amount: u64) -> anchor_lang::Result<WithdrawQuote> {
+        // withdraw NFT wali position pe fail hota hai, to quote bhi (NFT holder withdraw_nft_position use kare)
+        ctx.accounts.stake_info.require_not_tokenized()?;
+        let now = Clock::get()?.unix_timestamp;
+        let quote = quote_withdrawal(&ctx.accounts.config, &ctx.accounts.stake_info, amount, now)?;
+        let mut config = (*ctx.accounts.config).clone();
+        config.consume_withdraw_budget(amount, now)?;
+        Ok(quote)
+    }
+
+    // Quote Rewards: Read-only. User ka abhi tak ka claimable reward (current boost ke saath).
+    // Config aur ledger ki copy pe wahi accrual/settle chalta hai jo claim me, state nahi badalti.
+    pub fn quote_rewards(ctx: Context<QuoteRewards>, _user: Pubkey) -> anchor_lang::Result<u64> {
+        let mut config = (*ctx.accounts.config).clone();
+        let mut stake_info = (*ctx.accounts.stake_info).clone();
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        Ok(stake_info.pending_rewards)
+    }
+
+    // Verify Solvency: Koi bhi call kar sakta hai. Vault balances ko recorded liabilities se compare
+    // karke surplus (+) ya deficit (-) event me emit karta hai. Auditors ke liye sasta on-chain check.
+    pub fn verify_solvency(ctx: Context<VerifySolvency>) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        // Pehle accrual update, taaki reward liability abhi tak ki ho
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+
+        let vault_balance = ctx.accounts.vault.amount;
+        let vault_liabilities = config.principal_liabilities();
+
+        // Rewards shuru ho chuke hain to reward vault dena zaroori hai
+        let reward_vault_balance = match &ctx.accounts.reward_vault {
+            Some(reward_vault) => reward_vault.amount,
+            None => {
+                require!(config.reward_mint == Pubkey::default(), ErrorCode::RewardAccountsMissing);
+                0
+            }
+        };
+        let reward_liabilities = config.reward_liabilities();
+
+        let vault_surplus = vault_balance as i128 - vault_liabilities as i128;
+        let reward_surplus = reward_vault_balance as i128 - reward_liabilities as i128;
+
+        emit!(SolvencyChecked {
+            vault_balance,
+            vault_liabilities,
+            vault_surplus,
+            reward_vault_balance,
+            reward_liabilities,
+            reward_surplus,
+        });
+
+        msg!("Solvency: vault surplus {}, reward vault surplus {}", vault_surplus, reward_surplus);
+        Ok(())
+    }
+
+    // Sweep Surplus: Admin staking vault me liabilities se upar pada extra (galti se bheje tokens waghera)
+    // nikal sakta hai. Principal aur referral ka hissa kabhi touch nahi hota.
+    pub fn sweep_surplus(ctx: Context<SweepSurplus>, amount: u64) -> anchor_lang::Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let liabilities = ctx.accounts.config.principal_liabilities();
+        let surplus = (ctx.accounts.vault.amount as u128).saturating_sub(liabilities);
+        require!(amount as u128 <= surplus, ErrorCode::InsufficientSurplus);
+
+        transfer_from_vault(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            &ctx.accounts.mint,
+            ctx.bumps.vault,
+            amount,
+        )?;
+
+        emit!(SurplusSwept {
+            admin: ctx.accounts.admin.key(),
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+
+        msg!("Swept {} surplus tokens", amount);
+        Ok(())
+    }
+
+    // Initialize Insurance: Admin insurance vault PDA banata hai (staked mint ka token account).
+    pub fn initialize_insurance(ctx: Context<InitializeInsurance>) -> anchor_lang::Result<()> {
+        msg!("Insurance vault created: {}", ctx.accounts.insurance_vault.key());
+        Ok(())
+    }
+
+    // Update Insurance Share: Withdraw fee ka kitna hissa insurance vault me jaye (Basis Points).
+    pub fn update_insurance_share(ctx: Context<UpdateFee>, new_share_bps: u64) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        // Referral + insurance milake fee ke 100% se jyada nahi ho sakte
+        require!(
+            new_share_bps.saturating_add(config.referral_fee_share_bps) <= 10000,
+            ErrorCode::InvalidInsuranceShare
+        );
+
+        let old_share = config.insurance_fee_share_bps;
+        config.insurance_fee_share_bps = new_share_bps;
+
+        emit!(InsuranceShareUpdated {
+            old_share,
+            new_share: new_share_bps,
+        });
+
+        msg!("Insurance share updated from {} to {}", old_share, new_share_bps);
+        Ok(())
+    }
+
+    // Cover Loss: Incident ke baad admin insurance fund se staking vault ka deficit bharta hai,
+    // taaki stakers ka principal pura mil sake. Sirf deficit tak hi bhar sakte hain (surplus banana allowed nahi).
+    pub fn cover_loss(ctx: Context<CoverLoss>, amount: u64) -> anchor_lang::Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let liabilities = ctx.accounts.config.principal_liabilities();
+        let deficit = liabilities.saturating_sub(ctx.accounts.vault.amount as u128);
+        require!(amount as u128 <= deficit, ErrorCode::NoLossToCover);
+
+        let vault_before = ctx.accounts.vault.amount;
+        let mint_key = ctx.accounts.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"insurance",
+            mint_key.as_ref(),
+            &[ctx.bumps.insurance_vault]
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.insurance_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.insurance_vault.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        // Transfer fee wale mint me vault ko kam milta hai, event me wahi dikhao jo aaya
+        let received = received_by(&mut ctx.accounts.vault, vault_before)?;
+
+        emit!(LossCovered {
+            admin: ctx.accounts.admin.key(),
+            amount: received,
+            deficit_before: deficit,
+            deficit_after: deficit.saturating_sub(received as u128),
+        });
+
+        msg!("Covered {} of {} deficit from insurance", received, deficit);
+        Ok(())
+    }
+
+    // Set Slashing Authority: Admin slashing role kisi wallet ko deta hai (default = slashing band).
+    pub fn set_slashing_authority(ctx: Context<UpdateFee>, new_authority: Pubkey) -> anchor_lang::Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_authority = config.slashing_authority;
+        config.slashing_authority = new_authority;
+
+        emit!(SlashingAuthorityUpdated {
+            old_authority,
+            new_authority,
+        });
+
+        msg!("Slashing authority set to {}", new_authority);
+        Ok(())
+    }
+
+    // Slash: Slashing authority kisi position ke principal ka `bps` hissa penalty me kaat sakta hai.
+    // Kata hua paisa insurance vault me jata hai, destination na ho to burn hota hai. Koi aur
+    // destination allowed nahi, taaki slashing authority paisa apne wallet me na bhej sake.
+    // Is program me withdraw turant hota hai (koi unbonding queue nahi), isliye poora staked
+    // principal slashable hai. Pending rewards slash nahi hote.
+    pub fn slash(ctx: Context<Slash>, bps: u64, reason: u16) -> anchor_lang::Result<()> {
+        require!(bps > 0 && bps <= 10000, ErrorCode::InvalidSlashBps);
+
+        let config = &mut ctx.accounts.config;
+        let stake_info = &mut ctx.accounts.stake_info;
+        require!(stake_info.amount > 0, ErrorCode::InvalidAmount);
+
+        // Penalty round down hoti hai (staker ke favour me)
+        let (slashed, remaining) = checked_split_bps(stake_info.amount, bps)?;
+        require!(slashed > 0, ErrorCode::InvalidAmount);
+        // NFT wali position poori slash nahi hoti: amount 0 pe NFT redeem nahi ho sakta (pending rewards
+        // holder tak nahi pahunchte) aur ledger wapis wallet ko dena holder ka reward wallet ko de dega
+        require!(
+            remaining > 0 || stake_info.position_mint == Pubkey::default(),
+            ErrorCode::PositionTokenized
+        );
+
+        // 1. Rewards pehle settle, taaki slash se pehle ka reward na kate
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        let old_weight = stake_info.weight()?;
+
+        // 2. Vault se slashed tokens nikalo (destination me ya burn)
+        let burned = ctx.accounts.destination.is_none();
+        match &ctx.accounts.destination {
+            Some(destination) => transfer_from_vault(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                destination.to_account_info(),
+                &ctx.accounts.mint,
+                ctx.bumps.vault,
+                slashed,
+            )?,
+            None => {
+                let mint_key = ctx.accounts.mint.key();
+                let signer_seeds: &[&[&[u8]]] = &[&[
+                    b"vault",
+                    mint_key.as_ref(),
+                    &[ctx.bumps.vault]
+                ]];
+                token_interface::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        token_interface::Burn {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            from: ctx.accounts.vault.to_account_info(),
+                            authority: ctx.accounts.vault.to_account_info(),
+                        },
+                        signer_seeds
+                    ),
+                    slashed,
+                )?;
+            }
+        }
+
+        // 3. Ledger update
+        stake_info.amount = remaining;
+        commit_weight(config, stake_info, old_weight)?;
+        config.remove_stake(remaining, slashed)?;
+
+        emit!(Slashed {
+            position: stake_info.key(),
+            slasher: ctx.accounts.slashing_authority.key(),
+            bps,
+            reason,
+            amount: slashed,
+            remaining,
+            destination: ctx.accounts.destination.as_ref().map(|d| d.key()).unwrap_or_default(),
+            burned,
+        });
+
+        msg!("Slashed {} tokens ({} bps, reason {}). Remaining: {}", slashed, bps, reason, remaining);
+        Ok(())
+    }
+
+    // Airdrop Rewards: Admin ek transaction me kai stakers ko one-off bonus deta hai.
+    // `entries` me (staker, amount) aur remaining_accounts me usi order me unke UserStakeInfo PDAs.
+    // Poora total admin ke account se reward vault me jata hai aur har staker ke pending_rewards me
+    // judta hai; baad me normal claim_rewards se nikalta hai.
+    pub fn airdrop_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AirdropRewards<'info>>,
+        entries: Vec<AirdropEntry>,
+    ) -> anchor_lang::Result<()> {
+        require!(!entries.is_empty(), ErrorCode::InvalidAirdrop);
+        require!(ctx.remaining_accounts.len() == entries.len(), ErrorCode::InvalidAirdrop);
+
+        let mut total: u64 = 0;
+        for entry in entries.iter() {
+            require!(entry.amount > 0, ErrorCode::InvalidAmount);
+            total = checked_add(total, entry.amount)?;
+        }
+
+        // 1. Funding: admin -> reward vault. Poora total pahunchna chahiye (fee wale mint pe kam aaye to fail).
+        let vault_before = ctx.accounts.reward_vault.amount;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.admin_token_account.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.reward_vault.to_account_info(),
+                    authority: ctx.accounts.admin.to_account_info(),
+                },
+            ),
+            total,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+        let received = received_by(&mut ctx.accounts.reward_vault, vault_before)?;
+        require!(received >= total, ErrorCode::RewardVaultUnderfunded);
+
+        // 2. Har staker ka ledger credit karo (account wahi PDA hona chahiye jo staker se banta hai)
+        for (entry, account_info) in entries.iter().zip(ctx.remaining_accounts.iter()) {
+            let (expected, _) = Pubkey::find_program_address(&[b"user", entry.staker.as_ref()], ctx.program_id);
+            require_keys_eq!(account_info.key(), expected, ErrorCode::AirdropAccountMismatch);
+            require!(account_info.is_writable, ErrorCode::AirdropAccountMismatch);
+
+            let mut stake_info: Account<UserStakeInfo> = Account::try_from(account_info)?;
+            stake_info.pending_rewards = checked_add(stake_info.pending_rewards, entry.amount)?;
+            stake_info.exit(ctx.program_id)?;
+
+            emit!(RewardsAirdropped {
+                staker: entry.staker,
+                amount: entry.amount,
+                pending_rewards: stake_info.pending_rewards,
+            });
+        }
+
+        // 3. Naya reward liability ke hisaab me (solvency check isi se milata hai)
+        let config = &mut ctx.accounts.config;
+        config.rewards_accrued = checked_add_u128(config.rewards_accrued, total)?;
+
+        emit!(AirdropCompleted {
+            admin: ctx.accounts.admin.key(),
+            recipients: entries.len() as u32,
+            total,
+        });
+
+        msg!("Airdropped {} reward tokens to {} stakers", total, entries.len());
+        Ok(())
+    }
+
+    // Initialize Distributor: Admin off-chain compute hue bade distributions (jaise retroactive rewards)
+    // ke liye `mint` ka Merkle distributor aur uska vault banata hai. Rounds baad me set hote hain.
+    pub fn initialize_distributor(ctx: Context<InitializeDistributor>) -> anchor_lang::Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.mint = ctx.accounts.mint.key();
+        distributor.bump = ctx.bumps.distributor;
+        msg!("Distributor created for mint {}", distributor.mint);
+        Ok(())
+    }
+
+    // Set Distribution Round: Naya Merkle root, total aur expiry. Admin poora total abhi fund karta hai.
+    // Pichle round ka kuch bhi baaki (outstanding) na ho tabhi. Vault ka raw balance nahi dekhte, warna
+    // koi bhi dust bhej ke naya round rok sakta hai. Aisa dust agle clawback me nikal jata hai.
+    pub fn set_distribution_round(
+        ctx: Context<SetDistributionRound>,
+        root: [u8; 32],
+        total_amount: u64,
+        expiry_ts: i64,
+    ) -> anchor_lang::Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(root != [0u8; 32] && total_amount > 0 && expiry_ts > now, ErrorCode::InvalidDistributionRound);
+        require!(ctx.accounts.distributor.outstanding_amount == 0, ErrorCode::DistributionStillActive);
+
+        // Funding: admin -> distributor vault, poora total pahunchna chahiye
+        let vault_before = ctx.accounts.distributor_vault.amount;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.admin_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.distributor_vault.to_account_info(),
+                    authority: ctx.accounts.admin.to_account_info(),
+                },
+            ),
+            total_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        let received = received_by(&mut ctx.accounts.distributor_vault, vault_before)?;
+        require!(received >= total_amount, ErrorCode::RewardVaultUnderfunded);
+
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.round = checked_add(distributor.round, 1)?;
+        distributor.merkle_root = root;
+        distributor.total_amount = total_amount;
+        distributor.claimed_amount = 0;
+        distributor.outstanding_amount = total_amount;
+        distributor.num_claimed = 0;
+        distributor.expiry_ts = expiry_ts;
+        distributor.claimed_bitmap = [0u8; MAX_DISTRIBUTOR_CLAIMS / 8];
+
+        emit!(DistributionRoundSet {
+            mint: distributor.mint,
+            round: distributor.round,
+            merkle_root: root,
+            total_amount,
+            expiry_ts,
+        });
+
+        msg!("Distribution round {} set. Total: {}, Expiry: {}", distributor.round, total_amount, expiry_ts);
+        Ok(())
+    }
+
+    // Claim: Claimant apna (index, amount) Merkle proof ke saath dikha ke distributor vault se leta hai.
+    // Leaf = keccak(index_le || claimant || amount_le). Har index ek round me ek hi baar claim hota hai.
+    pub fn claim(ctx: Context<Claim>, index: u64, amount: u64, proof: Vec<[u8; 32]>) -> anchor_lang::Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let distributor = &mut ctx.accounts.distributor;
+        require!(distributor.round > 0 && now < distributor.expiry_ts, ErrorCode::DistributionExpired);
+        require!((index as usize) < MAX_DISTRIBUTOR_CLAIMS, ErrorCode::InvalidClaimIndex);
+        require!(!distributor.is_claimed(index), ErrorCode::AlreadyClaimed);
+        check_not_blocked(&ctx.accounts.claimant_blocklist)?;
+        check_not_blocked(&ctx.accounts.recipient_blocklist)?;
+
+        let claimant = ctx.accounts.claimant.key();
+        let leaf = distribution_leaf(index, claimant, amount);
+        require!(verify_merkle_proof(&proof, distributor.merkle_root, leaf), ErrorCode::InvalidMerkleProof);
+
+        // Root galat bana ho to bhi round ke total se jyada nahi nikal sakta
+        let claimed_amount = checked_add(distributor.claimed_amount, amount)?;
+        require!(claimed_amount <= distributor.total_amount, ErrorCode::InvalidDistributionRound);
+        distributor.claimed_amount = claimed_amount;
+        distributor.outstanding_amount = checked_sub(distributor.outstanding_amount, amount)?;
+        distributor.num_claimed = checked_add(distributor.num_claimed, 1)?;
+        distributor.set_claimed(index);
+
+        let mint_key = ctx.accounts.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"distributor_vault",
+            mint_key.as_ref(),
+            &[ctx.bumps.distributor_vault]
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.distributor_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.recipient.to_account_info(),
+                    authority: ctx.accounts.distributor_vault.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        emit!(DistributionClaimed {
+            round: distributor.round,
+            index,
+            claimant,
+            amount,
+        });
+
+        msg!("Claimed {} from distribution round {} (index {})", amount, distributor.round, index);
+        Ok(())
+    }
+
+    // Clawback Distribution: Round expire hone ke baad admin unclaimed paisa (aur vault me pada dust) wapis leta hai.
+    pub fn clawback_distribution(ctx: Context<ClawbackDistribution>) -> anchor_lang::Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let distributor = &mut ctx.accounts.distributor;
+        require!(distributor.round > 0 && now >= distributor.expiry_ts, ErrorCode::DistributionStillActive);
+
+        let amount = ctx.accounts.distributor_vault.amount;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let mint_key = ctx.accounts.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"distributor_vault",
+            mint_key.as_ref(),
+            &[ctx.bumps.distributor_vault]
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.distributor_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.distributor_vault.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        distributor.outstanding_amount = 0;
+
+        emit!(DistributionClawedBack {
+            round: distributor.round,
+            amount,
+            destination: ctx.accounts.destination.key(),
+        });
+
+        msg!("Clawed back {} unclaimed tokens from round {}", amount, distributor.round);
+        Ok(())
+    }
+
+    // Migrate Config: Upgrade se pehle bana config (sirf admin + withdraw_fee_bps) naye layout tak badhao.
+    // Naye fields zero se shuru hote hain. Fee vault yahin set hota hai, aur total_staked vault ke balance
+    // se, taaki jo positions abhi migrate nahi hui unka principal bhi liability me gina jaye.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> anchor_lang::Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        {
+            let data = config_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && data[..8] == GlobalConfig::DISCRIMINATOR,
+                anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+            );
+            let admin = Pubkey::try_from(&data[8..40]).unwrap();
+            require_keys_eq!(admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        }
+        let space = 8 + GlobalConfig::INIT_SPACE;
+        if config_info.data_len() >= space {
+            msg!("Config already uses the current layout");
+            return Ok(());
+        }
+        grow_account(&config_info, &ctx.accounts.admin, &ctx.accounts.system_program, space)?;
+
+        let mut config = GlobalConfig::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+        config.fee_vault = ctx.accounts.fee_vault.key();
+        config.total_staked = ctx.accounts.vault.amount;
+        config.last_update_ts = Clock::get()?.unix_timestamp;
+        config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+        emit!(ConfigMigrated {
+            fee_vault: config.fee_vault,
+            total_staked: config.total_staked,
+        });
+
+        msg!("Config migrated to {} bytes. Total staked: {}", space, config.total_staked);
+        Ok(())
+    }
+
+    // Migrate Stake Info: Upgrade se pehle bana ledger (sirf amount + deposit_ts) naye layout tak badhao.
+    // Koi bhi call kar sakta hai, rent ka farak payer deta hai. Config pehle migrate hona chahiye.
+    // Principal migrate_config me gina ja chuka hai; position yahan se reward weight aur staker count me judti hai.
+    pub fn migrate_stake_info(ctx: Context<MigrateStakeInfo>) -> anchor_lang::Result<()> {
+        let info = ctx.accounts.stake_info.to_account_info();
+        {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == UserStakeInfo::DISCRIMINATOR,
+                anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+            );
+        }
+        let space = 8 + UserStakeInfo::INIT_SPACE;
+        if info.data_len() >= space {
+            msg!("Stake info already uses the current layout");
+            return Ok(());
+        }
+        grow_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, space)?;
+
+        let config = &mut ctx.accounts.config;
+        config.accrue_rewards(Clock::get()?.unix_timestamp)?;
+        let mut stake_info = UserStakeInfo::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        stake_info.loyalty_start_ts = stake_info.deposit_ts;
+        if stake_info.amount > 0 {
+            config.active_stakers = checked_add(config.active_stakers, 1)?;
+        }
+        commit_weight(config, &mut stake_info, 0)?; // Reward yahin se milna shuru
+        stake_info.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(StakeInfoMigrated {
+            owner: ctx.accounts.owner.key(),
+            amount: stake_info.amount,
+        });
+
+        msg!("Stake info migrated. Amount: {}", stake_info.amount);
+        Ok(())
+    }
+}
+
+// ----------------- LEDGER HELPERS -----------------
+
+// Purane layout wale account ko `space` bytes tak badhao (naye bytes zero). Rent ka farak `payer` deta hai.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system: &Program<'info, System>,
+    space: usize,
+) -> anchor_lang::Result<()> {
+    let top_up = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.realloc(space, true)?;
+    Ok(())
+}
+
+// Withdraw ka shared flow. `settle = false` (emergency withdraw) me reward accrual/settle aur
+// boost refresh skip hote hain: sirf principal nikalta hai, last settle ke baad ka reward chhoot jata hai.
+fn process_withdraw<'info>(
+    ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+    amount: u64,
+    settle: bool,
+) -> anchor_lang::Result<()> {
+    let stake_info = &mut ctx.accounts.stake_info;
+    let staker = &mut ctx.accounts.staker;
+
+    // 1. Balance checks aur fee math (quote_withdraw bhi yahi use karta hai)
+    let now = Clock::get()?.unix_timestamp;
+    let quote = quote_withdrawal(&ctx.accounts.config, stake_info, amount, now)?;
+    let remaining = quote.remaining;
+    // NFT wali position sirf withdraw_nft_position se nikalti hai
+    stake_info.require_not_tokenized()?;
+    // Blocked staker nikal nahi sakta, aur blocked wallet ko paisa bhej bhi nahi sakte
+    check_not_blocked(&ctx.accounts.staker_blocklist)?;
+    check_not_blocked(&ctx.accounts.recipient_blocklist)?;
+
+    // Withdraw se pehle reward settle karo. Pending rewards withdraw ke baad bhi claim ho sakte hain.
+    let config = &mut ctx.accounts.config;
+    config.consume_withdraw_budget(amount, now)?; // Window ka budget khatam ho to ruk jao
+    if settle {
+        config.accrue_rewards(now)?;
+        stake_info.settle_rewards(config.acc_reward_per_share)?;
+        refresh_boost(config, stake_info, staker.key(), ctx.accounts.boost_account.as_ref())?;
+    }
+    let old_weight = stake_info.weight()?;
+
+    let total_amount = amount; // Kitna principal nikalna hai
+    let fee_amount = quote.fee;
+    let user_amount = quote.user_amount;
+
+    // Referrer ka hissa vault me hi rehta hai aur uske claimable me judta hai
+    let referral_fee = credit_referral(
+        config,
+        stake_info,
+        ctx.accounts.referral_account.as_mut(),
+        staker.key(),
+        fee_amount,
+    )?;
+    // Insurance ka hissa insurance vault me jata hai, baaki admin ko
+    let insurance_fee = quote.insurance_fee;
+    let admin_fee = checked_sub(checked_sub(fee_amount, referral_fee)?, insurance_fee)?;
+    send_to_insurance(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.vault,
+        ctx.accounts.insurance_vault.as_ref(),
+        &ctx.accounts.mint,
+        ctx.bumps.vault,
+        insurance_fee,
+    )?;
+
+    let bump = ctx.bumps.vault;                 // PDA ka bump seed
+    let mint_key = ctx.accounts.mint.key();     // Token ka mint address
+    let decimals = ctx.accounts.mint.decimals;  // transfer_checked ke liye
+
+    // PDA Seeds for Signing (Vault khud sign karega)
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        mint_key.as_ref(),
+        &[bump]
+    ]];
+
+    // 2a. Transfer Fee (Vault -> Fee Vault, ya fee split ke recipients)
+    pay_admin_fee(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.vault,
+        &ctx.accounts.fee_vault,
+        &config.fee_recipients,
+        ctx.remaining_accounts,
+        &ctx.accounts.mint,
+        bump,
+        admin_fee,
+    )?;
+
+    // 2b. Transfer Remaining Tokens (Vault -> Recipient)
+    // Recipient koi bhi wallet ho sakta hai (cold wallet / custodian), staker ka signature hi permission hai.
+    let user_transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_interface::TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.recipient.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds // Ye seeds proof hain ki program hi owner hai
+    );
+    token_interface::transfer_checked(user_transfer_ctx, user_amount, decimals)?;
+
+    // 3. Update User Ledger (User ka khata kam karo)
+    stake_info.amount = remaining;
+    commit_weight(config, stake_info, old_weight)?;
+    config.remove_stake(remaining, total_amount)?;
+    config.record_withdrawal(total_amount, fee_amount)?;
+
+    // 4. Emit Event (Log)
+    emit!(TokensWithdrawn {
+        staker: staker.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount: user_amount,
+        fee: fee_amount,
+        fee_discount_bps: quote.fee_discount_bps,
+        total_staked: remaining,
+        pool: config.stats(),
+    });
+
+    msg!("Withdrawn {} tokens. Fee deducted: {}. Remaining: {}", user_amount, fee_amount, remaining);
+    Ok(())
+}
+
+// Deposit caps (0 = unlimited). Deposit aur compound dono principal badhate hain, isliye dono yahi check chalate hain.
+fn check_stake_caps(config: &GlobalConfig, current: u64, amount: u64) -> anchor_lang::Result<()> {
+    require!(
+        config.max_total_staked == 0 || checked_add(config.total_staked, amount)? <= config.max_total_staked,
+        ErrorCode::PoolCapExceeded
+    );
+    require!(
+        config.max_per_user == 0 || checked_add(current, amount)? <= config.max_per_user,
+        ErrorCode::UserCapExceeded
+    );
+    Ok(())
+}
+
+// Deposit ka common ledger update: pehle pool ka reward accrue karo aur user ka pending settle karo
+// (warna naya amount purane time ka reward bhi le lega), phir amount aur pool total badhao.
+// `resets_loyalty` sirf owner ke apne deposit pe true hai; khaali position me har deposit loyalty shuru karta hai.
+fn record_deposit(
+    config: &mut GlobalConfig,
+    stake_info: &mut UserStakeInfo,
+    amount: u64,
+    owner: Pubkey,
+    resets_loyalty: bool,
+    boost_account: Option<&UncheckedAccount>,
+) -> anchor_lang::Result<()> {
+    // NFT wali position me wallet-keyed deposit nahi ho sakta (owner NFT holder hai)
+    stake_info.require_not_tokenized()?;
+
+    let new_amount = checked_add(stake_info.amount, amount)?;
+
+    // Position minimum size se chhoti nahi honi chahiye (dust positions rent aur indexers ka kachra hain)
+    require!(new_amount >= config.min_position, ErrorCode::BelowMinimumPosition);
+
+    check_stake_caps(config, stake_info.amount, amount)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    config.accrue_rewards(now)?;
+    stake_info.settle_rewards(config.acc_reward_per_share)?;
+    refresh_boost(config, stake_info, owner, boost_account)?;
+    let old_weight = stake_info.weight()?;
+
+    if resets_loyalty || stake_info.amount == 0 {
+        stake_info.loyalty_start_ts = now;
+    }
+    config.add_stake(stake_info.amount, amount)?; // Pool ka total (aur active stakers) bhi badhao
+    config.cumulative_deposits = checked_add_u128(config.cumulative_deposits, amount)?;
+    stake_info.amount = new_amount; // Amount add kar diya
+    stake_info.deposit_ts = now; // Abhi ka time store kiya
+    commit_weight(config, stake_info, old_weight)?;
+    Ok(())
+}
+
+// Blocklist check: entry token_contract ka `[b"blocked", wallet]` PDA hai (seeds account struct me
+// verify hote hain). Account khaali hai matlab wallet blocked nahi hai.
+fn check_not_blocked(blocklist_entry: &AccountInfo) -> anchor_lang::Result<()> {
+    require!(!token_contract::is_blocked(blocklist_entry), ErrorCode::WalletBlocked);
+    Ok(())
+}
+
+// Optional accounts pe seeds constraint nahi lag sakta, isliye yahan PDA khud derive karke check karo.
+fn check_not_blocked_for(wallet: &Pubkey, blocklist_entry: &AccountInfo) -> anchor_lang::Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[b"blocked", wallet.as_ref()], &token_contract::ID);
+    require_keys_eq!(blocklist_entry.key(), expected, ErrorCode::BlocklistAccountMismatch);
+    check_not_blocked(blocklist_entry)
+}
+
+// Allowlist check: pool allowlisted hai to entry zaroori hai. Proof se bani entry tabhi valid
+// hai jab uska root abhi bhi config ka root ho. (Entry ka wallet seeds se already verify hota hai.)
+fn check_allowlist(config: &GlobalConfig, entry: Option<&Account<AllowlistEntry>>) -> anchor_lang::Result<()> {
+    if !config.allowlist_enabled {
+        return Ok(());
+    }
+
+    let entry = entry.ok_or(ErrorCode::NotAllowlisted)?;
+    require!(
+        entry.merkle_root == [0u8; 32] || entry.merkle_root == config.allowlist_root,
+        ErrorCode::NotAllowlisted
+    );
+    Ok(())
+}
+
+// Withdraw ke saare checks aur fee split bina state badle. `withdraw` aur `quote_withdraw` dono
+// isi ko call karte hain, taaki frontend ko wahi number dikhe jo asli withdraw me katega.
+fn quote_withdrawal(
+    config: &GlobalConfig,
+    stake_info: &UserStakeInfo,
+    amount: u64,
+    now: i64,
+) -> anchor_lang::Result<WithdrawQuote> {
+    // Check Balance (Khate me paisa hai bhi ya nahi?)
+    require!(stake_info.amount > 0, ErrorCode::InvalidWithdraw);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(amount <= stake_info.amount, ErrorCode::InsufficientStake);
+
+    // Partial withdraw ke baad bacha hua stake minimum se kam (dust) nahi hona chahiye.
+    // Aisa ho to user ko pura amount nikalna padega.
+    let remaining = checked_sub(stake_info.amount, amount)?;
+    require!(
+        remaining == 0 || remaining >= config.min_position,
+        ErrorCode::WithdrawLeavesDust
+    );
+
+    let fee_discount_bps = config.loyalty_discount_bps(stake_info, now);
+    let (fee, user_amount) = config.discounted_withdraw_fee(amount, fee_discount_bps)?;
+    let referral_fee = if stake_info.referrer == Pubkey::default() {
+        0
+    } else {
+        config.referral_share(fee)?
+    };
+    let insurance_fee = config.insurance_share(fee)?;
+
+    Ok(WithdrawQuote {
+        amount,
+        fee,
+        fee_discount_bps,
+        referral_fee,
+        insurance_fee,
+        user_amount,
+        remaining,
+    })
+}
+
+// Withdraw fee me se referrer ka hissa (referral_fee_share_bps) uske referral account me credit karo.
+// Ye tokens vault me hi rehte hain (config.referral_owed liability) jab tak referrer claim na kare.
+// Agar position ka referrer set hai to uska referral account dena zaroori hai.
+fn credit_referral(
+    config: &mut GlobalConfig,
+    stake_info: &UserStakeInfo,
+    referral_account: Option<&mut Account<ReferralAccount>>,
+    staker: Pubkey,
+    fee_amount: u64,
+) -> anchor_lang::Result<u64> {
+    if stake_info.referrer == Pubkey::default() {
+        return Ok(0);
+    }
+
+    let referral_account = referral_account.ok_or(ErrorCode::ReferralAccountRequired)?;
+    require_keys_eq!(referral_account.referrer, stake_info.referrer, ErrorCode::ReferralAccountMismatch);
+
+    let referral_fee = config.referral_share(fee_amount)?;
+    if referral_fee == 0 {
+        return Ok(0);
+    }
+
+    referral_account.claimable = checked_add(referral_account.claimable, referral_fee)?;
+    referral_account.total_earned = checked_add(referral_account.total_earned, referral_fee)?;
+    config.referral_owed = checked_add(config.referral_owed, referral_fee)?;
+
+    emit!(ReferralFeeCredited {
+        referrer: referral_account.referrer,
+        staker,
+        amount: referral_fee,
+    });
+    Ok(referral_fee)
+}
+
+// Amount ya boost change hone ke baad: pool ka total weight naye weight se update karo
+// aur user ka reward debt sync karo. `old_weight` change se pehle ka weight hai.
+fn commit_weight(config: &mut GlobalConfig, stake_info: &mut UserStakeInfo, old_weight: u64) -> anchor_lang::Result<()> {
+    config.total_weight = checked_add(checked_sub(config.total_weight, old_weight)?, stake_info.weight()?)?;
+    stake_info.sync_reward_debt(config.acc_reward_per_share)
+}
+
+// Registered boost ko dobara verify karo. Settle ke baad call hota hai: tokens escrow me locked hain, isliye
+// pichhle settle se ab tak boost sach me laga tha. Sirf admin ke tier badalne (tier hata, mint / collection
+// badla, min_balance badha) se boost 0 hota hai; escrow aur registration rehte hain, tokens unregister_boost
+// se wapis milte hain. Tier ka multiplier admin ne badla ho to naya multiplier lag jata hai.
+fn refresh_boost(
+    config: &mut GlobalConfig,
+    stake_info: &mut UserStakeInfo,
+    owner: Pubkey,
+    boost_account: Option<&UncheckedAccount>,
+) -> anchor_lang::Result<()> {
+    if stake_info.boost_account == Pubkey::default() {
+        return Ok(());
+    }
+
+    let account = boost_account.ok_or(ErrorCode::BoostAccountRequired)?;
+    require_keys_eq!(account.key(), stake_info.boost_account, ErrorCode::BoostAccountMismatch);
+
+    let new_bps = config
+        .boost_tiers
+        .get(stake_info.boost_tier as usize)
+        .filter(|tier| holds_boost(account, tier, stake_info.boost_collection))
+        .map_or(0, |tier| tier.multiplier_bps);
+
+    let old_weight = stake_info.weight()?;
+    if new_bps == 0 && stake_info.boost_bps != 0 {
+        emit!(BoostRemoved {
+            owner,
+            boost_account: account.key(),
+        });
+    }
+    stake_info.boost_bps = new_bps;
+    commit_weight(config, stake_info, old_weight)
+}
+
+// Kya boost escrow (apna hi authority) abhi bhi tier ki requirement poori karta hai? Collection tier me
+// register ke waqt verify hua collection tier ke mint se match hona chahiye.
+fn holds_boost(account: &AccountInfo, tier: &BoostTier, boost_collection: Pubkey) -> bool {
+    if account.owner != &anchor_spl::token::ID && account.owner != &anchor_spl::token_2022::ID {
+        return false;
+    }
+    let Ok(data) = account.try_borrow_data() else {
+        return false;
+    };
+    let Ok(escrow) = TokenAccount::try_deserialize(&mut &data[..]) else {
+        return false;
+    };
+    let in_tier = if tier.collection {
+        boost_collection == tier.mint
+    } else {
+        escrow.mint == tier.mint
+    };
+    in_tier && escrow.owner == account.key() && escrow.amount >= tier.min_balance
+}
+
+// NFT ka verified Metaplex collection. `metadata` mint ka metadata PDA hona chahiye
+// (`[b"metadata", METADATA_PROGRAM_ID, mint]`, Token Metadata program ka account).
+fn verified_collection(metadata: &AccountInfo, mint: &Pubkey) -> Option<Pubkey> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &METADATA_PROGRAM_ID,
+    );
+    if metadata.key() != expected || metadata.owner != &METADATA_PROGRAM_ID {
+        return None;
+    }
+    let data = metadata.try_borrow_data().ok()?;
+    parse_verified_collection(&data, mint)
+}
+
+// Metadata v1 (borsh): key, update_authority, mint, name, symbol, uri, seller_fee_basis_points,
+// creators: Option<Vec<Creator>>, primary_sale_happened, is_mutable, edition_nonce: Option<u8>,
+// token_standard: Option<u8>, collection: Option<{ verified, key }>. Collection na ho ya unverified ho to None.
+pub fn parse_verified_collection(data: &[u8], mint: &Pubkey) -> Option<Pubkey> {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if data.len() < len {
+            return None;
+        }
+        let (head, tail) = data.split_at(len);
+        *data = tail;
+        Some(head)
+    }
+    fn take_u32(data: &mut &[u8]) -> Option<usize> {
+        Some(u32::from_le_bytes(take(data, 4)?.try_into().ok()?) as usize)
+    }
+    fn take_option(data: &mut &[u8]) -> Option<bool> {
+        match take(data, 1)?[0] {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    let mut data = data;
+    if take(&mut data, 1)?[0] != METADATA_KEY_V1 {
+        return None;
+    }
+    take(&mut data, 32)?; // update_authority
+    if take(&mut data, 32)? != mint.as_ref() {
+        return None;
+    }
+    for _ in 0..3 {
+        let len = take_u32(&mut data)?; // name, symbol, uri
+        take(&mut data, len)?;
+    }
+    take(&mut data, 2)?; // seller_fee_basis_points
+    if take_option(&mut data)? {
+        let creators = take_u32(&mut data)?;
+        take(&mut data, creators.checked_mul(34)?)?; // address + verified + share
+    }
+    take(&mut data, 2)?; // primary_sale_happened, is_mutable
+    for _ in 0..2 {
+        if take_option(&mut data)? {
+            take(&mut data, 1)?; // edition_nonce, token_standard
+        }
+    }
+    if !take_option(&mut data)? {
+        return None;
+    }
+    let verified = take(&mut data, 1)?[0] == 1;
+    let key = Pubkey::try_from(take(&mut data, 32)?).ok()?;
+    verified.then_some(key)
+}
+
+// ----------------- MERKLE HELPERS -----------------
+
+// Distributor claim ka leaf: keccak(index (u64 LE) || claimant || amount (u64 LE))
+pub fn distribution_leaf(index: u64, claimant: Pubkey, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[&index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).0
+}
+
+// Sorted-pair keccak Merkle proof verify karo (OpenZeppelin MerkleProof jaisa).
+// Har step pe chhota hash pehle aata hai, isliye proof me left/right batane ki zaroorat nahi.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof.iter() {
+        computed = if computed <= *node {
+            keccak::hashv(&[&computed, node]).0
+        } else {
+            keccak::hashv(&[node, &computed]).0
+        };
+    }
+    computed == root
+}
+
+// ----------------- TOKEN HELPERS -----------------
+
+// Staking vault se tokens bhejne ka common helper. Vault PDA apne seeds se sign karta hai.
+fn transfer_from_vault<'info>(
+    token_program: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    bump: u8,
+    amount: u64,
+) -> anchor_lang::Result<()> {
+    let mint_key = mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        mint_key.as_ref(),
+        &[bump]
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program,
+        token_interface::TransferChecked {
+            from: vault.clone(),
+            mint: mint.to_account_info(),
+            to,
+            authority: vault,
+        },
+        signer_seeds
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, mint.decimals)
+}
+
+// Reward vault se tokens bhejne ka common helper. Reward vault PDA apne seeds se sign karta hai.
+fn transfer_from_reward_vault<'info>(
+    token_program: AccountInfo<'info>,
+    reward_vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    bump: u8,
+    amount: u64,
+) -> anchor_lang::Result<()> {
+    let reward_mint_key = reward_mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"reward_vault",
+        reward_mint_key.as_ref(),
+        &[bump]
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program,
+        token_interface::TransferChecked {
+            from: reward_vault.clone(),
+            mint: reward_mint.to_account_info(),
+            to,
+            authority: reward_vault,
+        },
+        signer_seeds
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, reward_mint.decimals)
+}
+
+// Withdraw fee ka insurance wala hissa staking vault se insurance vault me bhejo.
+// Hissa 0 ho to kuch nahi; warna insurance vault dena zaroori hai.
+fn send_to_insurance<'info>(
+    token_program: AccountInfo<'info>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    insurance_vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault_bump: u8,
+    amount: u64,
+) -> anchor_lang::Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let insurance_vault = insurance_vault.ok_or(ErrorCode::InsuranceVaultRequired)?;
+    transfer_from_vault(
+        token_program,
+        vault.to_account_info(),
+        insurance_vault.to_account_info(),
+        mint,
+        vault_bump,
+        amount,
+    )
+}
+
+// Admin ka fee hissa bhejo. Fee split set nahi hai to sab fee_vault me, warna har recipient ko
+// uska weight wala hissa (dust last recipient ko). Recipients ke accounts remaining_accounts me
+// usi order me aane chahiye jo config me hai.
+#[allow(clippy::too_many_arguments)]
+fn pay_admin_fee<'info>(
+    token_program: AccountInfo<'info>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    fee_vault: &InterfaceAccount<'info, TokenAccount>,
+    recipients: &[FeeRecipient],
+    remaining_accounts: &[AccountInfo<'info>],
+    mint: &InterfaceAccount<'info, Mint>,
+    vault_bump: u8,
+    amount: u64,
+) -> anchor_lang::Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    if recipients.is_empty() {
+        return transfer_from_vault(token_program, vault.to_account_info(), fee_vault.to_account_info(), mint, vault_bump, amount);
+    }
+
+    require!(remaining_accounts.len() >= recipients.len(), ErrorCode::FeeRecipientsMissing);
+    let weights: Vec<u64> = recipients.iter().map(|r| r.bps as u64).collect();
+    let shares = math::split_by_weights(amount, &weights).ok_or(ErrorCode::MathOverflow)?;
+    for ((recipient, account), share) in recipients.iter().zip(remaining_accounts).zip(shares) {
+        require_keys_eq!(account.key(), recipient.token_account, ErrorCode::FeeRecipientMismatch);
+        if share > 0 {
+            transfer_from_vault(token_program.clone(), vault.to_account_info(), account.clone(), mint, vault_bump, share)?;
+        }
+    }
+    Ok(())
+}
+
+// Transfer ke baad vault me sach me kitna aaya. Token-2022 transfer fee extension wale mint me
+// ye bheje gaye amount se kam hota hai; ledger me hamesha yahi credit karte hain.
+fn received_by(vault: &mut InterfaceAccount<TokenAccount>, balance_before: u64) -> anchor_lang::Result<u64> {
+    vault.reload()?;
+    let received = checked_sub(vault.amount, balance_before)?;
+    require!(received > 0, ErrorCode::InvalidAmount);
+    Ok(received)
+}
+
+// ----------------- REWARD MATH -----------------
+// Emission schedule ko time ke upar integrate karke reward nikalte hain.
+
+// Schedule ke rules: kam se kam ek segment, MAX_EMISSION_SEGMENTS se zyada nahi,
+// start_ts negative nahi aur strictly badhte hue, aur halving interval negative nahi.
+fn validate_emission_schedule(segments: &[EmissionSegment], halving_interval: i64) -> anchor_lang::Result<()> {
+    require!(
+        !segments.is_empty() && segments.len() <= MAX_EMISSION_SEGMENTS,
+        ErrorCode::InvalidEmissionSchedule
+    );
+    require!(halving_interval >= 0, ErrorCode::InvalidEmissionSchedule);
+    require!(segments[0].start_ts >= 0, ErrorCode::InvalidEmissionSchedule);
+    require!(
+        segments.windows(2).all(|w| w[0].start_ts < w[1].start_ts),
+        ErrorCode::InvalidEmissionSchedule
+    );
+    Ok(())
+}
+
+// [from, to) window me total kitne reward tokens emit hue.
+// Har segment apne start_ts se agle segment ke start_ts tak chalta hai (last wala hamesha).
+// Agar halving_interval > 0 hai to segment ka rate har interval ke baad aadha hota hai,
+// aur halving ki ginti har naye segment ke start se dobara shuru hoti hai.
+pub fn emitted_between(
+    segments: &[EmissionSegment],
+    halving_interval: i64,
+    from: i64,
+    to: i64,
+) -> Option<u128> {
+    let mut total: u128 = 0;
+    if to <= from {
+        return Some(0);
+    }
+
+    for (i, segment) in segments.iter().enumerate() {
+        let seg_end = segments.get(i + 1).map(|s| s.start_ts).unwrap_or(i64::MAX);
+        let start = from.max(segment.start_ts);
+        let end = to.min(seg_end);
+        if start >= end {
+            continue;
+        }
+
+        if halving_interval == 0 {
+            let elapsed = end.checked_sub(start)? as u128;
+            total = total.checked_add(elapsed.checked_mul(segment.rate as u128)?)?;
+            continue;
+        }
+
+        // Halving epochs me tod ke integrate karo. 64 halvings ke baad rate 0 ho jata hai.
+        let mut epoch = start.checked_sub(segment.start_ts)? / halving_interval;
+        let mut cursor = start;
+        while cursor < end && epoch < 64 {
+            let epoch_end = segment
+                .start_ts
+                .saturating_add(halving_interval.saturating_mul(epoch + 1))
+                .min(end);
+            let rate = segment.rate >> epoch;
+            if rate == 0 {
+                break;
+            }
+            let elapsed = epoch_end.checked_sub(cursor)? as u128;
+            total = total.checked_add(elapsed.checked_mul(rate as u128)?)?;
+            cursor = epoch_end;
+            epoch += 1;
+        }
+    }
+
+    Some(total)
+}
+
+// ----------------- CHECKED MATH -----------------
+// Token math ka shared layer `token_contract::math` me hai (u128 intermediate, division round down).
+// Ye chhote wrappers overflow/underflow ko panic ki jagah MathOverflow error bana dete hain.
+
+fn checked_add(a: u64, b: u64) -> anchor_lang::Result<u64> {
+    a.checked_add(b).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+fn checked_sub(a: u64, b: u64) -> anchor_lang::Result<u64> {
+    a.checked_sub(b).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+fn checked_add_u128(a: u128, b: u64) -> anchor_lang::Result<u128> {
+    a.checked_add(b as u128).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+// amount ko (bps wala hissa, baaki) me todta hai. bps > 10000 ho to bhi MathOverflow.
+fn checked_split_bps(amount: u64, bps: u64) -> anchor_lang::Result<(u64, u64)> {
+    math::split_bps(amount, bps).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+impl GlobalConfig {
+    // Position me stake juda (deposit / compound). `prev_amount` position ka purana amount hai,
+    // 0 se upar gaya to naya active staker.
+    pub fn add_stake(&mut self, prev_amount: u64, amount: u64) -> anchor_lang::Result<()> {
+        if prev_amount == 0 && amount > 0 {
+            self.active_stakers = checked_add(self.active_stakers, 1)?;
+        }
+        self.total_staked = checked_add(self.total_staked, amount)?;
+        Ok(())
+    }
+
+    // Position se stake nikla. `remaining` 0 hua to staker active nahi raha.
+    pub fn remove_stake(&mut self, remaining: u64, amount: u64) -> anchor_lang::Result<()> {
+        if remaining == 0 && amount > 0 {
+            self.active_stakers = checked_sub(self.active_stakers, 1)?;
+        }
+        self.total_staked = checked_sub(self.total_staked, amount)?;
+        Ok(())
+    }
+
+    // Withdraw ke cumulative stats (principal aur fee)
+    pub fn record_withdrawal(&mut self, amount: u64, fee: u64) -> anchor_lang::Result<()> {
+        self.cumulative_withdrawals = checked_add_u128(self.cumulative_withdrawals, amount)?;
+        self.cumulative_fees = checked_add_u128(self.cumulative_fees, fee)?;
+        Ok(())
+    }
+
+    // Withdraw rate limit: `amount` ko current window ke budget me se ghatao. Window khatam ho gayi ho
+    // to nayi window shuru hoti hai aur uska TVL snapshot lete hain (bps limit isi pe lagti hai).
+    pub fn consume_withdraw_budget(&mut self, amount: u64, now: i64) -> anchor_lang::Result<()> {
+        if self.withdraw_window_secs == 0 {
+            return Ok(());
+        }
+        if now >= self.withdraw_window_start.saturating_add(self.withdraw_window_secs) {
+            self.withdraw_window_start = now;
+            self.withdraw_window_tvl = self.total_staked;
+            self.withdraw_window_used = 0;
+        }
+
+        let mut budget = u64::MAX;
+        if self.withdraw_window_max_amount > 0 {
+            budget = self.withdraw_window_max_amount;
+        }
+        if self.withdraw_window_max_bps > 0 {
+            budget = budget.min(checked_split_bps(self.withdraw_window_tvl, self.withdraw_window_max_bps)?.0);
+        }
+
+        let used = checked_add(self.withdraw_window_used, amount)?;
+        require!(used <= budget, ErrorCode::WithdrawLimitExceeded);
+        self.withdraw_window_used = used;
+        Ok(())
+    }
+
+    // Reward vault se reward nikla (claim / compound / bounty)
+    pub fn record_rewards_paid(&mut self, amount: u64) -> anchor_lang::Result<()> {
+        self.rewards_paid = checked_add_u128(self.rewards_paid, amount)?;
+        Ok(())
+    }
+
+    // Withdraw fee split: (fee, user ko milne wala amount). Fee round down hoti hai (user ke favour me).
+    pub fn withdraw_fee(&self, amount: u64) -> anchor_lang::Result<(u64, u64)> {
+        checked_split_bps(amount, self.withdraw_fee_bps)
+    }
+
+    // Position pe lagne wala sabse bada loyalty discount (bps). Duration last deposit se ginte hain.
+    pub fn loyalty_discount_bps(&self, stake_info: &UserStakeInfo, now: i64) -> u64 {
+        let held = now.saturating_sub(stake_info.loyalty_start_ts);
+        self.loyalty_tiers
+            .iter()
+            .filter(|tier| held >= tier.min_duration && stake_info.amount >= tier.min_amount)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Discount ke baad (fee, user ko milega). fee = amount * fee_bps * (10000 - discount) / 10000^2,
+    // ek hi baar round down, taaki discount ki wajah se fee kabhi exact se upar na jaye.
+    pub fn discounted_withdraw_fee(&self, amount: u64, discount_bps: u64) -> anchor_lang::Result<(u64, u64)> {
+        if discount_bps == 0 {
+            return self.withdraw_fee(amount);
+        }
+        require!(self.withdraw_fee_bps <= math::BPS_DENOMINATOR, ErrorCode::MathOverflow);
+        let kept_bps = checked_sub(math::BPS_DENOMINATOR, discount_bps)?;
+        let fee = math::mul_div_floor(
+            amount,
+            self.withdraw_fee_bps * kept_bps, // max 10000 * 10000, u64 me aaram se
+            math::BPS_DENOMINATOR * math::BPS_DENOMINATOR,
+        )
+        .ok_or(ErrorCode::MathOverflow)?;
+        Ok((fee, checked_sub(amount, fee)?))
+    }
+
+    // Fee me se referrer ka hissa (round down, dust admin ko jata hai)
+    pub fn referral_share(&self, fee: u64) -> anchor_lang::Result<u64> {
+        Ok(checked_split_bps(fee, self.referral_fee_share_bps)?.0)
+    }
+
+    // Fee me se insurance fund ka hissa (round down, dust admin ko jata hai)
+    pub fn insurance_share(&self, fee: u64) -> anchor_lang::Result<u64> {
+        Ok(checked_split_bps(fee, self.insurance_fee_share_bps)?.0)
+    }
+
+    // Staking vault ki liabilities: sabka principal + referrers ka unclaimed hissa
+    pub fn principal_liabilities(&self) -> u128 {
+        self.total_staked as u128 + self.referral_owed as u128
+    }
+
+    // Reward vault ki liabilities: accrue hua par abhi tak nahi nikla reward
+    pub fn reward_liabilities(&self) -> u128 {
+        self.rewards_accrued.saturating_sub(self.rewards_paid)
+    }
+
+    // Events ke liye pool stats ka snapshot
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            total_staked: self.total_staked,
+            active_stakers: self.active_stakers,
+            cumulative_deposits: self.cumulative_deposits,
+            cumulative_withdrawals: self.cumulative_withdrawals,
+            cumulative_fees: self.cumulative_fees,
+        }
+    }
+
+    // Pool ka reward accumulator `now` tak aage badhao. Reward weight (boost ke saath) ke hisaab se bantta hai.
+    // Agar koi stake nahi hai to us time ka emission kisi ko nahi milta.
+    pub fn accrue_rewards(&mut self, now: i64) -> anchor_lang::Result<()> {
+        if now <= self.last_update_ts {
+            return Ok(());
+        }
+
+        if self.total_weight > 0 {
+            let emitted = emitted_between(
+                &self.emission_segments,
+                self.halving_interval,
+                self.last_update_ts,
+                now,
+            )
+            .ok_or(ErrorCode::MathOverflow)?;
+
+            let increment = math::mul_div_floor_u128(emitted, REWARD_PRECISION, self.total_weight as u128)
+                .ok_or(ErrorCode::MathOverflow)?;
+            self.acc_reward_per_share = self
+                .acc_reward_per_share
+                .checked_add(increment)
+                .ok_or(ErrorCode::MathOverflow)?;
+            // Stakers ko itna reward de diya (rounding dust bhi isme hai, to liability thodi upar hi rehti hai)
+            self.rewards_accrued = self
+                .rewards_accrued
+                .checked_add(emitted)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        self.last_update_ts = now;
+        Ok(())
+    }
+}
+
+impl UserStakeInfo {
+    // Reward weight = amount x boost multiplier. Boost nahi hai to weight = amount.
+    pub fn weight(&self) -> anchor_lang::Result<u64> {
+        if self.boost_bps == 0 {
+            return Ok(self.amount);
+        }
+        math::bps_of(self.amount, self.boost_bps).ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    // Boost hata do (weight wapis amount ke barabar)
+    pub fn clear_boost(&mut self) {
+        self.boost_account = Pubkey::default();
+        self.boost_collection = Pubkey::default();
+        self.boost_tier = 0;
+        self.boost_bps = 0;
+    }
+
+    // User ke stake ka total reward (accumulator ke hisaab se), abhi tak ka.
+    // Chhote total_weight pe accumulator bahut bada ho sakta hai, isliye product 256-bit me banta hai.
+    fn accumulated(&self, acc_reward_per_share: u128) -> anchor_lang::Result<u128> {
+        math::mul_div_floor_u128(self.weight()? as u128, acc_reward_per_share, REWARD_PRECISION)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    // Last settle ke baad jo reward bana wo pending_rewards me daalo.
+    pub fn settle_rewards(&mut self, acc_reward_per_share: u128) -> anchor_lang::Result<()> {
+        let accumulated = self.accumulated(acc_reward_per_share)?;
+        let earned = accumulated.saturating_sub(self.reward_debt);
+        let earned = u64::try_from(earned).map_err(|_| ErrorCode::MathOverflow)?;
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(earned)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.reward_debt = accumulated;
+        Ok(())
+    }
+
+    // NFT se represent hui position ko wallet-keyed instructions touch nahi kar sakte.
+    pub fn require_not_tokenized(&self) -> anchor_lang::Result<()> {
+        require_keys_eq!(self.position_mint, Pubkey::default(), ErrorCode::PositionTokenized);
+        Ok(())
+    }
+
+    // Amount change hone ke baad reward debt naye amount ke hisaab se set karo.
+    pub fn sync_reward_debt(&mut self, acc_reward_per_share: u128) -> anchor_lang::Result<()> {
+        self.reward_debt = self.accumulated(acc_reward_per_share)?;
+        Ok(())
+    }
+}
+
+// ----------------- ERRORS -----------------
+// Custom Errors jo humne banaye hain
+#[error_code]
+pub enum ErrorCode {
+    #[msg("You are not authorized to perform this action.")]
+    Unauthorized, // Agar koi galat banda admin function call kare
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount, // Agar 0 ya negative deposit karne ki koshish kare
+    #[msg("No tokens to withdraw.")]
+    InvalidWithdraw, // Agar khali account se withdraw kare
+    #[msg("Rewards have already been initialized.")]
+    RewardsAlreadyInitialized, // Schedule dobara commit karne ki koshish
+    #[msg("Emission schedule is invalid.")]
+    InvalidEmissionSchedule, // Khali, unsorted ya bahut lamba schedule
+    #[msg("No rewards to claim.")]
+    NoRewardsToClaim, // Pending reward 0 hai
+    #[msg("Reward vault does not hold enough tokens.")]
+    RewardVaultUnderfunded, // Reward vault me paisa kam hai
+    #[msg("Arithmetic overflow.")]
+    MathOverflow, // Token / reward math overflow (ya bps 100% se upar)
+    #[msg("Reward mint does not match the staked mint.")]
+    RewardMintMismatch, // Compound sirf same mint pe ho sakta hai
+    #[msg("Auto compound is not enabled for this position.")]
+    AutoCompoundDisabled, // User ne crank ke liye opt-in nahi kiya
+    #[msg("Compound bounty exceeds the allowed maximum.")]
+    InvalidBountyBps, // Bounty MAX_COMPOUND_BOUNTY_BPS se zyada
+    #[msg("Position transfers are disabled.")]
+    PositionTransfersDisabled, // Admin ne transfers pause kiye hain
+    #[msg("New owner must be a different wallet.")]
+    InvalidNewOwner, // Khud ko transfer nahi kar sakte
+    #[msg("Destination already has an active position.")]
+    PositionNotEmpty, // Naye owner ka stake pehle se hai
+    #[msg("Position is represented by an NFT.")]
+    PositionTokenized, // NFT wali position pe wallet-keyed action
+    #[msg("Reward vault and reward recipient are required to pay pending rewards.")]
+    RewardAccountsMissing, // NFT withdraw me reward accounts nahi diye
+    #[msg("Boost tier is invalid.")]
+    InvalidBoostTier, // Tier index galat ya multiplier range ke bahar
+    #[msg("Boost account does not meet the tier requirement.")]
+    BoostRequirementNotMet, // Mint galat ya balance kam
+    #[msg("The registered boost account must be provided.")]
+    BoostAccountRequired, // Boost registered hai par account nahi diya
+    #[msg("Boost account does not match the registered one.")]
+    BoostAccountMismatch, // Galat boost account diya
+    #[msg("Referral share must be at most 10000 bps.")]
+    InvalidReferralShare, // 100% se zyada share
+    #[msg("The referrer's referral account must be provided.")]
+    ReferralAccountRequired, // Referrer set hai par account nahi diya
+    #[msg("Referral account does not match the position's referrer.")]
+    ReferralAccountMismatch, // Galat referral account
+    #[msg("Deposit would exceed the pool's total stake cap.")]
+    PoolCapExceeded, // max_total_staked cross ho jayega
+    #[msg("Deposit would exceed the per-user stake cap.")]
+    UserCapExceeded, // max_per_user cross ho jayega
+    #[msg("Withdraw amount exceeds the staked amount.")]
+    InsufficientStake, // Stake se zyada nikalne ki koshish
+    #[msg("Position would be below the minimum stake.")]
+    BelowMinimumPosition, // Deposit ke baad bhi position min se chhoti
+    #[msg("Withdraw would leave less than the minimum stake; withdraw everything instead.")]
+    WithdrawLeavesDust, // Partial withdraw ke baad dust bachega
+    #[msg("Wallet is not on the pool allowlist.")]
+    NotAllowlisted, // Allowlist pool me entry nahi hai
+    #[msg("Merkle proof is invalid.")]
+    InvalidMerkleProof, // Proof root se match nahi karta
+    #[msg("Wallet is on the compliance blocklist")]
+    WalletBlocked, // Compliance ne wallet block kiya hai
+    #[msg("Sweep amount exceeds the vault surplus")]
+    InsufficientSurplus, // Liabilities ke upar itna extra nahi hai
+    #[msg("Slash bps must be between 1 and 10000")]
+    InvalidSlashBps, // 0 ya 100% se jyada
+    #[msg("Referral and insurance shares together cannot exceed 100% of the fee")]
+    InvalidInsuranceShare, // referral + insurance > 10000
+    #[msg("Insurance vault account is required")]
+    InsuranceVaultRequired, // Insurance share set hai par vault nahi diya
+    #[msg("Amount exceeds the staking vault deficit")]
+    NoLossToCover, // Deficit se jyada cover nahi kar sakte
+    #[msg("Fee split needs 1-5 unique recipients with non-zero weights summing to 10000 bps")]
+    InvalidFeeSplit, // Weights ka total 10000 nahi, duplicate ya bahut recipients
+    #[msg("Fee split recipient accounts must be passed as remaining accounts")]
+    FeeRecipientsMissing, // remaining_accounts me recipients kam hain
+    #[msg("Remaining account does not match the configured fee recipient")]
+    FeeRecipientMismatch, // Order ya account galat
+    #[msg("Loyalty tier discount must be 1-10000 bps with a non-negative duration (max 8 tiers)")]
+    InvalidLoyaltyTier, // Discount range ke bahar ya bahut tiers
+    #[msg("Withdraw limit needs a non-negative window, bps up to 10000 and at least one limit")]
+    InvalidWithdrawLimit, // Galat window / bps settings
+    #[msg("Withdrawal exceeds the remaining budget for this window; try again after the window resets")]
+    WithdrawLimitExceeded, // Is window ka budget khatam
+    #[msg("Airdrop needs at least one entry and one stake account per entry")]
+    InvalidAirdrop, // Khali list ya accounts ki ginti match nahi
+    #[msg("Remaining account is not the stake account of the airdrop staker")]
+    AirdropAccountMismatch, // Galat PDA ya writable nahi
+    #[msg("Distribution round needs a root, a non-zero total and a future expiry")]
+    InvalidDistributionRound, // Galat round settings (ya total se jyada claim)
+    #[msg("Distribution round is still active or has unclaimed funds")]
+    DistributionStillActive, // Pehle expire + clawback hona chahiye
+    #[msg("No active distribution round")]
+    DistributionExpired, // Round nahi hai ya expire ho gaya
+    #[msg("Claim index is out of range")]
+    InvalidClaimIndex, // Bitmap ke bahar
+    #[msg("This index has already been claimed")]
+    AlreadyClaimed, // Same index dobara
+    #[msg("Pool is deprecated; stake in the new pool instead")]
+    PoolDeprecated, // Deprecated pool me deposit
+    #[msg("Target program does not match the pool's migration target")]
+    MigrationTargetMismatch, // Galat / non-executable / set hi nahi
+    #[msg("Migrated tokens were not fully deposited into the target pool")]
+    MigrationIncomplete, // Naye pool ne poora amount nahi liya
+    #[msg("Fee vault does not match the pool's configured fee vault")]
+    FeeVaultMismatch, // Staker apna account fee vault bana ke nahi de sakta
+    #[msg("Blocklist account is missing or is not the blocklist PDA of the wallet")]
+    BlocklistAccountMismatch, // Optional payout account ka blocklist PDA galat / nahi diya
+    #[msg("Boost tokens are still escrowed; call unregister_boost first")]
+    BoostStillEscrowed, // Tokenize / transfer se pehle escrow wapis lo
+}
+
+// ----------------- STRUCTS (Data Validation) -----------------
+
+// Initialize ke liye validation logic
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,                               // Naya account banao
+        payer = payer,                      // Paise 'payer' dega is account ko banane ke
+        seeds = [b"vault", mint.key().as_ref()], // Ye account ek PDA hai (Address deterministic hai)
+        bump,                               // Bump seed collision bachane ke liye
+        token::mint = mint,                 // Ye account kis token ko hold karega
+        token::authority = vault,           // Iska owner ye khud (Vault PDA) hoga
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Ye wo account hai jahan sabka paisa store hoga
+
+    pub mint: InterfaceAccount<'info, Mint>,          // Token ka main address (e.g., USDC ka address)
+
+    #[account(token::mint = mint)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>, // Admin fee ka default account (config me save hota hai)
+
+    #[account(mut)]
+    pub payer: Signer<'info>,                // Jo fees pay karega (Admin)
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GlobalConfig::INIT_SPACE, // Discriminator + config fields (emission schedule ke saath)
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>, // Global Config Account
+
+    pub system_program: Program<'info, System>, // Solana system program (account creation ke liye zaroori)
+    pub token_program: Interface<'info, TokenInterface>,   // SPL Token ya Token-2022 program (mint jis program ka ho)
+    pub rent: Sysvar<'info, Rent>,              // Rent sysvar (rent calculation ke liye)
+}
+
+// Update Fee Validation
+#[derive(Accounts)]
+pub struct UpdateFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin, // Confirm karo ki signer hi admin hai
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub admin: Signer<'info>, // Sirf admin hi call kar sakta hai
+}
+
+// Set Fee Vault Validation
+#[derive(Accounts)]
+pub struct SetFeeVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"vault", mint.key().as_ref()], bump)]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Mint isi pool ka hai, ye proof
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+// Initialize Rewards Validation
+#[derive(Accounts)]
+pub struct InitializeRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()], // Reward tokens yahan rahenge
+        bump,
+        token::mint = reward_mint,
+        token::authority = reward_vault,    // Reward vault PDA khud owner hai
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>, // Reward token ka mint (staked mint bhi ho sakta hai)
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Deposit ke liye validation logic
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>, // Jo banda deposit kar raha hai (sign karega)
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()], // Wahi vault dhoondo jo initialize hua tha
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,                     // Agar user pehli baar aaya hai to account banao check karke
+        payer = staker,                     // Fees staker dega
+        space = 8 + UserStakeInfo::INIT_SPACE, // Kitni jagah chahiye (Discriminator + fields)
+        seeds = [b"user", staker.key().as_ref()], // Har user ka alag PDA hoga uske wallet address ke base pe
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>, // Ye user ka personal ledger hai
+
+    pub mint: InterfaceAccount<'info, Mint>, // Token Mint
+
+    #[account(mut)]
+    pub staker_token_account: InterfaceAccount<'info, TokenAccount>, // User ka token wallet jahan se paise katenge
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>, // Reward accrual aur total staked ke liye
+
+    pub referral_account: Option<Account<'info, ReferralAccount>>, // Optional referrer (sirf pehli baar set hota hai)
+
+    #[account(
+        seeds = [b"allowlist", staker.key().as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>, // Allowlist pool me zaroori
+
+    /// CHECK: Staker ka blocklist PDA (token_contract ka). Sirf exist karta hai ya nahi, wo dekhte hain.
+    #[account(seeds = [b"blocked", staker.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub staker_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Registered boost escrow. Data `refresh_boost` me khud verify hota hai,
+    /// kyunki tier badalne pe boost hatana hai (fail nahi karna).
+    pub boost_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Deposit For ke liye validation logic
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct DepositFor<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Jo paise de raha hai (token authority + rent)
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,                      // Naya ledger banane ka rent payer dega
+        space = 8 + UserStakeInfo::INIT_SPACE,
+        seeds = [b"user", beneficiary.as_ref()], // Position beneficiary ke naam pe
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>, // Payer ka token wallet
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Registered boost escrow. Data `refresh_boost` me khud verify hota hai,
+    /// kyunki tier badalne pe boost hatana hai (fail nahi karna).
+    pub boost_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"allowlist", beneficiary.as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>, // Beneficiary ki allowlist entry
+
+    /// CHECK: Payer ka blocklist PDA (token_contract ka). Sirf exist karta hai ya nahi, wo dekhte hain.
+    #[account(seeds = [b"blocked", payer.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub payer_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Beneficiary ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", beneficiary.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub beneficiary_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Withdraw ke liye validation logic
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>, // Jo banda withdraw maang raha hai
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Vault se paise nikalenge
+
+    #[account(
+        mut, // Modify karenge kyunki balance 0 karna hai
+        seeds = [b"user", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>, // User ka ledger check karenge
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint, // Recipient same token ka account hona chahiye
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>, // Jahan paisa jayega (staker ka ya koi aur wallet)
+
+    #[account(mut, address = config.fee_vault @ ErrorCode::FeeVaultMismatch)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>, // Admin ka account jahan fee jayegi
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>, // Fee rate aur reward accrual ke liye config chahiye
+
+    #[account(mut)]
+    pub referral_account: Option<Account<'info, ReferralAccount>>, // Position ke referrer ka account (agar set hai)
+
+    /// CHECK: Registered boost escrow. Data `refresh_boost` me khud verify hota hai,
+    /// kyunki tier badalne pe boost hatana hai (fail nahi karna).
+    pub boost_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance", mint.key().as_ref()],
+        bump,
+    )]
+    pub insurance_vault: Option<InterfaceAccount<'info, TokenAccount>>, // Insurance share set ho to zaroori
+
+    /// CHECK: Staker ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", staker.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub staker_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient wallet ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", recipient.owner.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Claim Rewards ke liye validation logic
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    pub staker: Signer<'info>, // Jo banda reward claim kar raha hai
+
+    #[account(
+        mut,
+        seeds = [b"user", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", config.reward_mint.as_ref()],
+        bump,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>, // Yahan se reward jayega
+
+    #[account(address = config.reward_mint @ ErrorCode::RewardMintMismatch)]
+    pub reward_mint: InterfaceAccount<'info, Mint>, // transfer_checked ke liye
+
+    #[account(
+        mut,
+        token::mint = config.reward_mint, // Reward mint ka hi account hona chahiye
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>, // Jahan reward jayega (staker ka ya koi aur wallet)
+
+    /// CHECK: Registered boost escrow. Data `refresh_boost` me khud verify hota hai,
+    /// kyunki tier badalne pe boost hatana hai (fail nahi karna).
+    pub boost_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Staker ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", staker.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub staker_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient wallet ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", recipient.owner.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Compound ke liye validation logic
+#[derive(Accounts)]
+pub struct Compound<'info> {
+    pub staker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Compounded reward yahan jayega
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", config.reward_mint.as_ref()],
+        bump,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == config.reward_mint @ ErrorCode::RewardMintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // Staked mint == reward mint hona chahiye
+
+    /// CHECK: Registered boost escrow. Data `refresh_boost` me khud verify hota hai,
+    /// kyunki tier badalne pe boost hatana hai (fail nahi karna).
+    pub boost_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Staker ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", staker.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub staker_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Auto compound opt-in ke liye validation logic
+#[derive(Accounts)]
+pub struct SetAutoCompound<'info> {
+    pub staker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+}
+
+// Tokenize Position ke liye validation logic
+#[derive(Accounts)]
+pub struct TokenizePosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>, // Position owner (mint + ATA ka rent dega)
+
+    #[account(
+        mut,
+        seeds = [b"user", owner.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>, // Boost hatane pe pool weight update
+
+    // Nonce pool-wide counter hai, ledger ka nahi: transfer_position ledger close kar deta hai aur
+    // dobara bana ledger phir 0 se shuru hota. Isse redeem ke baad position dobara tokenize ho sakti hai.
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"position_mint", stake_info.key().as_ref(), &config.positions_tokenized.to_le_bytes()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = position_mint,
+    )]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed, // ATA pehle se bana ho (client ne khud banaya) to bhi chalega
+        payer = owner,
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>, // NFT yahan mint hoga
+
+    /// CHECK: Owner ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", owner.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub owner_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Withdraw NFT Position ke liye validation logic
+#[derive(Accounts)]
+pub struct WithdrawNftPosition<'info> {
+    pub holder: Signer<'info>, // Jo abhi NFT hold karta hai
+
+    #[account(
+        mut,
+        constraint = stake_info.position_mint == position_mint.key() @ ErrorCode::PositionTokenized,
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>, // Position jiska NFT hai
+
+    #[account(mut)]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = position_mint,
+        token::authority = holder,
+        constraint = holder_nft_account.amount == 1 @ ErrorCode::Unauthorized,
+    )]
+    pub holder_nft_account: InterfaceAccount<'info, TokenAccount>, // NFT yahan se burn hoga
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>, // Principal yahan jayega
+
+    #[account(mut, address = config.fee_vault @ ErrorCode::FeeVaultMismatch)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", config.reward_mint.as_ref()],
+        bump,
+    )]
+    pub reward_vault: Option<InterfaceAccount<'info, TokenAccount>>, // Sirf tab chahiye jab pending rewards ho
+
+    #[account(
+        mut,
+        token::mint = config.reward_mint,
+    )]
+    pub reward_recipient: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = config.reward_mint @ ErrorCode::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub referral_account: Option<Account<'info, ReferralAccount>>, // Position ke referrer ka account (agar set hai)
+
+    #[account(
+        mut,
+        seeds = [b"insurance", mint.key().as_ref()],
+        bump,
+    )]
+    pub insurance_vault: Option<InterfaceAccount<'info, TokenAccount>>, // Insurance share set ho to zaroori
+
+    /// CHECK: Holder ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", holder.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub holder_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient wallet ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", recipient.owner.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Reward recipient ke owner ka blocklist PDA. reward_recipient optional hai isliye
+    /// seeds handler me `check_not_blocked_for` verify karta hai.
+    pub reward_recipient_blocklist: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Register Boost ke liye validation logic
+#[derive(Accounts)]
+pub struct RegisterBoost<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>, // Escrow ka rent bhi yahi deta hai
+
+    #[account(
+        mut,
+        seeds = [b"user", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub boost_mint: InterfaceAccount<'info, Mint>, // Partner token / NFT ka mint
+
+    #[account(
+        mut,
+        token::mint = boost_mint,
+        token::authority = staker, // Holding staker ki honi chahiye
+    )]
+    pub staker_boost_account: InterfaceAccount<'info, TokenAccount>, // Tokens yahan se escrow me jayenge
+
+    #[account(
+        init,
+        payer = staker,
+        seeds = [b"boost_escrow", stake_info.key().as_ref()],
+        bump,
+        token::mint = boost_mint,
+        token::authority = boost_escrow, // Escrow PDA khud authority hai
+    )]
+    pub boost_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Collection tier ke liye NFT ka Metaplex metadata. Owner aur PDA `verified_collection` check karta hai.
+    pub boost_metadata: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Unregister Boost ke liye validation logic
+#[derive(Accounts)]
+pub struct UnregisterBoost<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>, // Escrow ka rent wapis yahan
+
+    #[account(
+        mut,
+        seeds = [b"user", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub boost_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = boost_mint,
+        token::authority = staker,
+    )]
+    pub staker_boost_account: InterfaceAccount<'info, TokenAccount>, // Escrowed tokens yahan wapis aayenge
+
+    #[account(
+        mut,
+        seeds = [b"boost_escrow", stake_info.key().as_ref()],
+        bump,
+        token::mint = boost_mint,
+    )]
+    pub boost_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Register Referrer ke liye validation logic
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferralAccount::INIT_SPACE,
+        seeds = [b"referral", referrer.key().as_ref()], // Har referrer ka ek account
+        bump
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Quote Withdraw ke liye validation logic (read-only)
+#[derive(Accounts)]
+pub struct QuoteWithdraw<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub stake_info: Account<'info, UserStakeInfo>, // Jis position ka quote chahiye
+}
+
+// Quote Rewards ke liye validation logic (read-only)
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct QuoteRewards<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [b"user", user.as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+}
+
+// Initialize Insurance ke liye validation logic
+#[derive(Accounts)]
+pub struct InitializeInsurance<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"insurance", mint.key().as_ref()], // Insurance fund yahan rahega
+        bump,
+        token::mint = mint,
+        token::authority = insurance_vault, // Insurance vault PDA khud owner hai
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>, // Staked mint
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Cover Loss ke liye validation logic
+#[derive(Accounts)]
+pub struct CoverLoss<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance", mint.key().as_ref()],
+        bump,
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Deficit wala staking vault
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Airdrop Rewards ke liye validation logic (stakers ke UserStakeInfo remaining_accounts me)
+#[derive(Accounts)]
+pub struct AirdropRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", config.reward_mint.as_ref()],
+        bump,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>, // Bonus yahan jama hota hai
+
+    #[account(address = config.reward_mint @ ErrorCode::RewardMintMismatch)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = config.reward_mint,
+        token::authority = admin,
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>, // Bonus ka source
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Initialize Distributor ke liye validation logic
+#[derive(Accounts)]
+pub struct InitializeDistributor<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MerkleDistributor::INIT_SPACE,
+        seeds = [b"distributor", mint.key().as_ref()],
+        bump,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"distributor_vault", mint.key().as_ref()], // Distribution ka paisa yahan rahega
+        bump,
+        token::mint = mint,
+        token::authority = distributor_vault, // Vault PDA khud owner hai
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>, // Jis token me distribution hoga
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Set Distribution Round ke liye validation logic
+#[derive(Accounts)]
+pub struct SetDistributionRound<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor", mint.key().as_ref()],
+        bump = distributor.bump,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = admin,
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>, // Round ka funding source
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Claim ke liye validation logic
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    pub claimant: Signer<'info>, // Leaf me isi ka pubkey hona chahiye
+
+    #[account(
+        mut,
+        seeds = [b"distributor", mint.key().as_ref()],
+        bump = distributor.bump,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>, // Jahan claim jayega (claimant ka ya koi aur)
+
+    /// CHECK: Claimant ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", claimant.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub claimant_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient wallet ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", recipient.owner.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Clawback Distribution ke liye validation logic
+#[derive(Accounts)]
+pub struct ClawbackDistribution<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor", mint.key().as_ref()],
+        bump = distributor.bump,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>, // Unclaimed paisa yahan jayega
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Migrate Position ke liye validation logic (naye pool ke Deposit accounts remaining_accounts me)
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>, // Naye pool me bhi yahi staker (rent bhi yahi dega)
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = staker, // Naye pool ka deposit isi account se katega
+    )]
+    pub staker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(mut, address = config.fee_vault @ ErrorCode::FeeVaultMismatch)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>, // Live pool se migrate pe admin fee yahan
+
+    #[account(mut)]
+    pub referral_account: Option<Account<'info, ReferralAccount>>, // Live pool + referrer set ho to zaroori
+
+    #[account(
+        mut,
+        seeds = [b"insurance", mint.key().as_ref()],
+        bump,
+    )]
+    pub insurance_vault: Option<InterfaceAccount<'info, TokenAccount>>, // Live pool + insurance share set ho to zaroori
+
+    /// CHECK: Registered boost escrow. Data `refresh_boost` me khud verify hota hai.
+    pub boost_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Staker ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", staker.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub staker_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Naye pool ka program; config.migration_target se match hota hai (instruction me check).
+    pub target_program: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Slash ke liye validation logic
+#[derive(Accounts)]
+pub struct Slash<'info> {
+    pub slashing_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.slashing_authority == slashing_authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub stake_info: Account<'info, UserStakeInfo>, // Jis position ko slash karna hai
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>, // Burn me supply kam hoti hai
+
+    #[account(
+        mut,
+        seeds = [b"insurance", mint.key().as_ref()], // Sirf insurance vault
+        bump,
+    )]
+    pub destination: Option<InterfaceAccount<'info, TokenAccount>>, // None = burn
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Verify Solvency ke liye validation logic (permissionless)
+#[derive(Accounts)]
+pub struct VerifySolvency<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>, // Accrual update hoga
+
+    #[account(
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"reward_vault", config.reward_mint.as_ref()],
+        bump,
+    )]
+    pub reward_vault: Option<InterfaceAccount<'info, TokenAccount>>, // Rewards shuru hue ho to zaroori
+}
+
+// Sweep Surplus ke liye validation logic
+#[derive(Accounts)]
+pub struct SweepSurplus<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>, // Surplus yahan jayega
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Claim Referral Rewards ke liye validation logic
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump = referral_account.bump,
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Referral fee yahin pada hai
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Referrer ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", referrer.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub referrer_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient wallet ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", recipient.owner.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Add Allowlist Entry ke liye validation logic (Admin only)
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddAllowlistEntry<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AllowlistEntry::INIT_SPACE,
+        seeds = [b"allowlist", wallet.as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Remove Allowlist Entry ke liye validation logic (Admin only)
+#[derive(Accounts)]
+pub struct RemoveAllowlistEntry<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"allowlist", allowlist_entry.wallet.as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+// Verify Allowlist Proof ke liye validation logic
+#[derive(Accounts)]
+pub struct VerifyAllowlistProof<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>, // Jo allowlist me hone ka proof de raha hai
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed, // Root badalne ke baad naye proof se entry refresh ho sake
+        payer = wallet,
+        space = 8 + AllowlistEntry::INIT_SPACE,
+        seeds = [b"allowlist", wallet.key().as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Transfer Position ke liye validation logic
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct TransferPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>, // Current owner (naye ledger ka rent bhi yahi dega)
+
+    #[account(
+        mut,
+        close = owner, // Purana ledger band, rent owner ko wapis
+        seeds = [b"user", owner.key().as_ref()],
+        bump
+    )]
+    pub from_stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserStakeInfo::INIT_SPACE,
+        seeds = [b"user", new_owner.as_ref()],
+        bump
+    )]
+    pub to_stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Current owner ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", owner.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub owner_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Naye owner ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", new_owner.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub new_owner_blocklist: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Crank Compound ke liye validation logic (permissionless)
+#[derive(Accounts)]
+pub struct CrankCompound<'info> {
+    pub cranker: Signer<'info>, // Koi bhi bot/user
+
+    /// CHECK: Sirf stake_info PDA derive karne ke liye, iska data read nahi hota.
+    pub owner: UncheckedAccount<'info>, // Jis user ki position compound ho rahi hai
+
+    #[account(
+        mut,
+        seeds = [b"user", owner.key().as_ref()],
+        bump
+    )]
+    pub stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", config.reward_mint.as_ref()],
+        bump,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == config.reward_mint @ ErrorCode::RewardMintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = config.reward_mint,
+    )]
+    pub cranker_token_account: InterfaceAccount<'info, TokenAccount>, // Bounty yahan aayegi
+
+    /// CHECK: Registered boost escrow. Data `refresh_boost` me khud verify hota hai,
+    /// kyunki tier badalne pe boost hatana hai (fail nahi karna).
+    pub boost_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Position owner ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", owner.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub owner_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Cranker ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", cranker.key().as_ref()], bump, seeds::program = token_contract::ID)]
+    pub cranker_blocklist: UncheckedAccount<'info>,
+
+    /// CHECK: Bounty account ke owner ka blocklist PDA (token_contract ka).
+    #[account(seeds = [b"blocked", cranker_token_account.owner.as_ref()], bump, seeds::program = token_contract::ID)]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Config layout migration ke liye validation logic
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Purana layout `GlobalConfig` me deserialize nahi hota, isliye discriminator aur admin handler me check hote hain.
+    #[account(mut, seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Iska balance = ab tak ka saara principal
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>, // Admin fee ka account (config me save hota hai)
+
+    #[account(mut)]
+    pub admin: Signer<'info>, // Badhi hui jagah ka rent bhi yahi deta hai
+
+    pub system_program: Program<'info, System>,
+}
+
+// Stake info layout migration ke liye validation logic
+#[derive(Accounts)]
+pub struct MigrateStakeInfo<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Koi bhi, badhi hui jagah ka rent deta hai
+
+    /// CHECK: Sirf stake_info PDA derive karne ke liye, iska data read nahi hota.
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Purana layout `UserStakeInfo` me deserialize nahi hota, isliye discriminator handler me check hota hai.
+    #[account(mut, seeds = [b"user", owner.key().as_ref()], bump, owner = crate::ID)]
+    pub stake_info: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>, // Pehle migrate_config ho chuka hona chahiye
+
+    pub system_program: Program<'info, System>,
+}
+
+// ----------------- DATA ACCOUNTS -----------------
+// On-chain data storage structure specific to our program
+
+#[account]
+#[derive(InitSpace, Default)]
+pub struct UserStakeInfo {
+    pub amount: u64,        // Kitna paisa jama hai (8 bytes)
+    pub deposit_ts: i64,    // Kab jama kiya (Timestamp) (8 bytes)
+    pub reward_debt: u128,  // Accumulator ka wo hissa jo pehle hi settle ho chuka (16 bytes)
+    pub pending_rewards: u64, // Claim hone ka wait kar rahe rewards (8 bytes)
+    pub auto_compound: bool, // Crank ko compound karne ki permission (1 byte)
+    pub position_mint: Pubkey, // Position NFT ka mint (default = NFT nahi hai) (32 bytes)
+    pub boost_account: Pubkey, // Boost escrow (`b"boost_escrow" + stake_info`), default = boost nahi (32 bytes)
+    pub boost_tier: u8,        // config.boost_tiers me index (1 byte)
+    pub boost_bps: u64,        // Abhi laga multiplier (0 = boost nahi) (8 bytes)
+    pub referrer: Pubkey,      // Jisne refer kiya (default = koi nahi), ek baar set hota hai (32 bytes)
+    pub loyalty_start_ts: i64, // Loyalty kab se gin rahe hain; deposit_for ise reset nahi karta (8 bytes)
+    pub boost_collection: Pubkey, // Collection tier me escrowed NFT ka verified collection (32 bytes)
+}
+
+#[account]
+#[derive(InitSpace, Default)]
+pub struct GlobalConfig {
+    pub admin: Pubkey,       // Admin kaun hai
+    pub withdraw_fee_bps: u64, // Current Fee (Basis Points)
+    pub fee_vault: Pubkey,   // Admin fee ka account (fee split na ho to poori admin fee yahan)
+    pub compound_bounty_bps: u64, // Crank compound pe cranker ki bounty (Basis Points)
+    pub position_transfers_paused: bool, // true = transfer_position band hai
+    pub deprecated: bool, // true = naye deposits band, positions migrate_position se naye pool me
+    pub migration_target: Pubkey, // Naye pool ka program (deprecated hone pe set)
+    pub slashing_authority: Pubkey, // Positions slash kar sakta hai (default = slashing band)
+    pub referral_fee_share_bps: u64, // Withdraw fee ka kitna hissa referrer ko (Basis Points)
+    pub insurance_fee_share_bps: u64, // Withdraw fee ka kitna hissa insurance vault ko (Basis Points)
+    pub referral_owed: u64,  // Vault me pada referrers ka unclaimed hissa
+    pub max_total_staked: u64, // Pool ki max capacity (0 = unlimited)
+    pub max_per_user: u64,   // Ek position ki max size (0 = unlimited)
+    pub min_position: u64,   // Ek position ki min size (0 = koi minimum nahi)
+    pub withdraw_window_secs: i64, // Withdraw rate limit ki window (0 = limit band)
+    pub withdraw_window_max_amount: u64, // Ek window me max principal (0 = absolute limit nahi)
+    pub withdraw_window_max_bps: u64, // Ek window me window-start TVL ka max hissa (0 = bps limit nahi)
+    pub withdraw_window_start: i64, // Current window kab shuru hui
+    pub withdraw_window_tvl: u64, // Window shuru hote waqt total_staked
+    pub withdraw_window_used: u64, // Current window me ab tak kitna nikla
+    pub allowlist_enabled: bool, // true = sirf allowlisted wallets deposit kar sakte hain
+    pub allowlist_root: [u8; 32], // Allowlist ka Merkle root (keccak, sorted pairs)
+    pub total_staked: u64,   // Pool me kul kitna stake hai (principal)
+    pub active_stakers: u64, // Kitni positions me abhi stake > 0 hai
+    pub positions_tokenized: u64, // Ab tak kitne position NFTs bane (agle NFT mint ka nonce)
+    pub cumulative_deposits: u128, // Ab tak deposit/deposit_for se kul kitna aaya (u128 taaki kabhi overflow na ho)
+    pub cumulative_withdrawals: u128, // Ab tak kul kitna principal nikla (fee ke saath)
+    pub cumulative_fees: u128, // Ab tak kul withdraw fee (referrer ka hissa bhi)
+    pub total_weight: u64,   // Boost ke saath kul reward weight (reward isi pe bantta hai)
+    pub reward_mint: Pubkey, // Reward token (default = rewards abhi shuru nahi hue)
+    pub acc_reward_per_share: u128, // Har staked token pe ab tak ka reward (REWARD_PRECISION scaled)
+    pub last_update_ts: i64, // Accumulator last kab update hua
+    pub rewards_accrued: u128, // Ab tak stakers ke naam kitna reward accrue hua
+    pub rewards_paid: u128,  // Ab tak reward vault se kitna reward nikla (claim / compound / bounty)
+    pub halving_interval: i64, // Har segment ka rate itne seconds baad aadha (0 = halving off)
+    #[max_len(MAX_EMISSION_SEGMENTS)]
+    pub emission_segments: Vec<EmissionSegment>, // (start_ts, rate) list, start_ts ke order me
+    #[max_len(MAX_BOOST_TIERS)]
+    pub boost_tiers: Vec<BoostTier>, // Partner token / NFT holdings pe reward multipliers
+    #[max_len(MAX_FEE_RECIPIENTS)]
+    pub fee_recipients: Vec<FeeRecipient>, // Admin fee ka split (empty = sab fee_vault me)
+    #[max_len(MAX_LOYALTY_TIERS)]
+    pub loyalty_tiers: Vec<LoyaltyTier>, // Duration / amount ke hisaab se withdraw fee discount
+}
+
+// Referrer ka ledger: fee share yahan jama hota hai
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralAccount {
+    pub referrer: Pubkey,   // Referrer wallet
+    pub claimable: u64,     // Abhi claim ho sakta hai
+    pub total_earned: u64,  // Ab tak ka kul referral income
+    pub bump: u8,
+}
+
+// Allowlist entry: is wallet ko allowlist pool me deposit ki permission hai
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistEntry {
+    pub wallet: Pubkey,
+    pub merkle_root: [u8; 32], // Proof se bani entry ka root (admin entry ke liye zero)
+    pub bump: u8,
+}
+
+// Merkle distributor: ek mint ke off-chain compute hue distribution rounds
+#[account]
+#[derive(InitSpace)]
+pub struct MerkleDistributor {
+    pub mint: Pubkey,          // Distribution ka token
+    pub round: u64,            // Current round (0 = abhi koi round nahi)
+    pub merkle_root: [u8; 32], // Current round ka root
+    pub total_amount: u64,     // Round me kul kitna baantna hai
+    pub claimed_amount: u64,   // Ab tak kitna claim hua
+    pub num_claimed: u64,      // Kitne indexes claim hue
+    pub expiry_ts: i64,        // Iske baad claim band aur clawback khula
+    pub bump: u8,
+    pub claimed_bitmap: [u8; MAX_DISTRIBUTOR_CLAIMS / 8], // Har index ka ek bit (1 = claim ho gaya)
+    pub outstanding_amount: u64, // Round ka abhi tak na claim hua hissa (clawback pe 0)
+}
+
+impl MerkleDistributor {
+    pub fn is_claimed(&self, index: u64) -> bool {
+        self.claimed_bitmap[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u64) {
+        self.claimed_bitmap[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+// Boost tier: `mint` ke kam se kam `min_balance` tokens escrow me lock karne pe weight x multiplier_bps/10000
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct BoostTier {
+    pub mint: Pubkey,         // Partner token ka mint, ya `collection` = true ho to Metaplex collection ka mint
+    pub min_balance: u64,     // Kitne tokens lock karne hain (NFT ke liye 1)
+    pub multiplier_bps: u64,  // 10000 = 1x, 15000 = 1.5x
+    pub collection: bool,     // true = collection ka koi bhi verified NFT chalega
+}
+
+// Loyalty tier: position `min_duration` seconds purani aur `min_amount` ki ho to fee me discount_bps/10000 ki chhoot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct LoyaltyTier {
+    pub min_duration: i64,  // Last deposit se kitne seconds (0 = koi shart nahi)
+    pub min_amount: u64,    // Position ka min size (0 = koi shart nahi)
+    pub discount_bps: u64,  // 10000 = poori fee maaf, 2500 = fee ka 25% kam
+}
+
+// Airdrop ki ek line: `staker` ki position ke pending_rewards me `amount` judega
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AirdropEntry {
+    pub staker: Pubkey,
+    pub amount: u64,
+}
+
+// quote_withdraw ka result (withdraw ke exact numbers)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithdrawQuote {
+    pub amount: u64,       // Kitna principal nikalna hai
+    pub fee: u64,          // Kul withdraw fee (loyalty discount ke baad)
+    pub fee_discount_bps: u64, // Laga hua loyalty discount (fee ka kitna % maaf)
+    pub referral_fee: u64, // Fee me se referrer ka hissa (vault me rehta hai)
+    pub insurance_fee: u64, // Fee me se insurance vault ka hissa
+    pub user_amount: u64,  // Recipient ko kitna milega
+    pub remaining: u64,    // Withdraw ke baad position me kitna bachega
+}
+
+// Pool stats ka snapshot (deposit / withdraw events me jata hai)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolStats {
+    pub total_staked: u64,
+    pub active_stakers: u64,
+    pub cumulative_deposits: u128,
+    pub cumulative_withdrawals: u128,
+    pub cumulative_fees: u128,
+}
+
+// Emission schedule ka ek tukda: start_ts se rate tokens/second emit honge
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct EmissionSegment {
+    pub start_ts: i64, // Segment kab shuru hota hai (unix seconds)
+    pub rate: u64,     // Reward tokens per second (poore pool ke liye)
+}
+
+// ----------------- EVENTS -----------------
+// Ye logs hain jo frontend catch kar sakta hai bina chain state padhe
+
+#[event]
+pub struct TokensStaked {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub pool: PoolStats,
+}
+
+#[event]
+pub struct TokensStakedFor {
+    pub payer: Pubkey,       // Jisne tokens diye
+    pub beneficiary: Pubkey, // Jiski position bani
+    pub amount: u64,
+    pub total_staked: u64,
+    pub pool: PoolStats,
+}
+
+#[event]
+pub struct TokensWithdrawn {
+    pub staker: Pubkey,
+    pub recipient: Pubkey, // Token account jahan paisa gaya
+    pub amount: u64,
+    pub fee: u64,
+    pub fee_discount_bps: u64, // Fee pe laga loyalty discount
+    pub total_staked: u64,
+    pub pool: PoolStats,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub old_fee: u64,
+    pub new_fee: u64,
+}
+
+#[event]
+pub struct EmissionScheduleSet {
+    pub reward_mint: Pubkey,
+    pub segments: Vec<EmissionSegment>,
+    pub halving_interval: i64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub staker: Pubkey,
+    pub recipient: Pubkey, // Token account jahan reward gaya
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardsCompounded {
+    pub staker: Pubkey,
+    pub cranker: Pubkey, // Self compound me staker hi cranker hota hai
+    pub amount: u64,     // Kitna stake me juda
+    pub bounty: u64,     // Cranker ko kitna mila
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct AutoCompoundUpdated {
+    pub staker: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct PositionTokenized {
+    pub owner: Pubkey,
+    pub stake_info: Pubkey,
+    pub position_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct NftPositionWithdrawn {
+    pub holder: Pubkey,
+    pub position_mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub fee_discount_bps: u64,
+    pub rewards: u64,
+    pub pool: PoolStats,
+}
+
+#[event]
+pub struct BoostTiersUpdated {
+    pub tiers: Vec<BoostTier>,
+}
+
+#[event]
+pub struct DistributionRoundSet {
+    pub mint: Pubkey,
+    pub round: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub expiry_ts: i64,
+}
+
+#[event]
+pub struct DistributionClaimed {
+    pub round: u64,
+    pub index: u64,
+    pub claimant: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DistributionClawedBack {
+    pub round: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct RewardsAirdropped {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub pending_rewards: u64, // Airdrop ke baad kul claimable
+}
+
+#[event]
+pub struct AirdropCompleted {
+    pub admin: Pubkey,
+    pub recipients: u32,
+    pub total: u64,
+}
+
+#[event]
+pub struct LoyaltyTiersUpdated {
+    pub tiers: Vec<LoyaltyTier>,
+}
+
+#[event]
+pub struct FeeVaultUpdated {
+    pub fee_vault: Pubkey,
+}
+
+#[event]
+pub struct FeeSplitUpdated {
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[event]
+pub struct BoostRegistered {
+    pub owner: Pubkey,
+    pub boost_account: Pubkey,
+    pub tier: u8,
+    pub multiplier_bps: u64,
+}
+
+#[event]
+pub struct BoostRemoved {
+    pub owner: Pubkey,
+    pub boost_account: Pubkey,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferrerSet {
+    pub staker: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferralFeeCredited {
+    pub referrer: Pubkey,
+    pub staker: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralShareUpdated {
+    pub old_share: u64,
+    pub new_share: u64,
+}
+
+#[event]
+pub struct WithdrawLimitUpdated {
+    pub window_secs: i64,
+    pub max_amount: u64,
+    pub max_bps: u64,
+}
+
+#[event]
+pub struct DepositCapsUpdated {
+    pub max_total_staked: u64,
+    pub max_per_user: u64,
+}
+
+#[event]
+pub struct MinPositionUpdated {
+    pub old_min: u64,
+    pub new_min: u64,
+}
+
+#[event]
+pub struct AllowlistToggled {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct AllowlistRootUpdated {
+    pub root: [u8; 32],
+}
+
+#[event]
+pub struct AllowlistEntryAdded {
+    pub wallet: Pubkey,
+    pub by_proof: bool, // true = Merkle proof se, false = admin ne add kiya
+}
+
+#[event]
+pub struct AllowlistEntryRemoved {
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct PositionTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub pending_rewards: u64,
+    pub deposit_ts: i64, // Original deposit time carry hota hai
+}
+
+#[event]
+pub struct PoolDeprecated {
+    pub deprecated: bool,
+    pub migration_target: Pubkey,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub fee_vault: Pubkey,
+    pub total_staked: u64, // Vault ka balance migration ke waqt
+}
+
+#[event]
+pub struct StakeInfoMigrated {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PositionMigrated {
+    pub staker: Pubkey,
+    pub to_program: Pubkey,
+    pub amount: u64,    // Is pool se nikla principal
+    pub deposited: u64, // Naye pool me jama hua (fee aur transfer fee ke baad)
+    pub fee: u64,       // Live pool se migrate pe withdraw fee (deprecated pe 0)
+    pub fee_waived: bool,
+    pub pool: PoolStats,
+}
+
+#[event]
+pub struct PositionTransfersPaused {
+    pub paused: bool,
+}
+
+#[event]
+pub struct CompoundBountyUpdated {
+    pub old_bounty: u64,
+    pub new_bounty: u64,
+}
+
+#[event]
+pub struct SolvencyChecked {
+    pub vault_balance: u64,
+    pub vault_liabilities: u128,   // total_staked + referral_owed
+    pub vault_surplus: i128,       // Negative = deficit
+    pub reward_vault_balance: u64,
+    pub reward_liabilities: u128,  // Accrued par unclaimed rewards
+    pub reward_surplus: i128,      // Negative = deficit
+}
+
+#[event]
+pub struct SurplusSwept {
+    pub admin: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SlashingAuthorityUpdated {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct Slashed {
+    pub position: Pubkey,    // Slash hui position (UserStakeInfo)
+    pub slasher: Pubkey,
+    pub bps: u64,
+    pub reason: u16,         // Off-chain defined reason code
+    pub amount: u64,         // Kitna kata
+    pub remaining: u64,      // Position me kitna bacha
+    pub destination: Pubkey, // Burn hua ho to default
+    pub burned: bool,
+}
+
+#[event]
+pub struct InsuranceShareUpdated {
+    pub old_share: u64,
+    pub new_share: u64,
+}
+
+#[event]
+pub struct LossCovered {
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub deficit_before: u128,
+    pub deficit_after: u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn withdraw_fee_near_u64_max_does_not_overflow() {
+        let config = GlobalConfig { withdraw_fee_bps: 10000, ..Default::default() };
+        assert_eq!(config.withdraw_fee(u64::MAX).unwrap(), (u64::MAX, 0));
+
+        let config = GlobalConfig { withdraw_fee_bps: 300, ..Default::default() };
+        let (fee, user_amount) = config.withdraw_fee(u64::MAX).unwrap();
+        assert_eq!(fee, ((u64::MAX as u128 * 300) / 10000) as u64);
+        assert_eq!(fee + user_amount, u64::MAX);
+    }
+
+    #[test]
+    fn fee_above_100_percent_is_an_error() {
+        let config = GlobalConfig { withdraw_fee_bps: 10001, ..Default::default() };
+        assert!(config.withdraw_fee(1).is_err());
+    }
+
+    #[test]
+    fn fee_rounds_down_in_favour_of_the_user() {
+        let config = GlobalConfig { withdraw_fee_bps: 300, referral_fee_share_bps: 5000, ..Default::default() };
+        assert_eq!(config.withdraw_fee(50).unwrap(), (1, 49)); // 1.5 -> 1
+        assert_eq!(config.referral_share(1).unwrap(), 0); // 0.5 -> 0
+    }
+
+    #[test]
+    fn loyalty_discount_picks_best_matching_tier_and_rounds_down() {
+        let config = GlobalConfig {
+            withdraw_fee_bps: 300,
+            loyalty_tiers: vec![
+                LoyaltyTier { min_duration: 100, min_amount: 0, discount_bps: 2500 },
+                LoyaltyTier { min_duration: 0, min_amount: 1_000, discount_bps: 5000 },
+            ],
+            ..Default::default()
+        };
+        let small = UserStakeInfo { amount: 500, loyalty_start_ts: 0, ..Default::default() };
+        assert_eq!(config.loyalty_discount_bps(&small, 99), 0);
+        assert_eq!(config.loyalty_discount_bps(&small, 100), 2500);
+        let large = UserStakeInfo { amount: 1_000, loyalty_start_ts: 0, ..Default::default() };
+        assert_eq!(config.loyalty_discount_bps(&large, 100), 5000);
+
+        // 3% fee, 50% off: 1000 -> 15, 50 -> 0.75 -> 0
+        assert_eq!(config.discounted_withdraw_fee(1_000, 5000).unwrap(), (15, 985));
+        assert_eq!(config.discounted_withdraw_fee(50, 5000).unwrap(), (0, 50));
+        assert_eq!(config.discounted_withdraw_fee(1_000, 10000).unwrap(), (0, 1_000));
+        assert_eq!(config.discounted_withdraw_fee(u64::MAX, 0).unwrap(), config.withdraw_fee(u64::MAX).unwrap());
+    }
+
+    #[test]
+    fn withdraw_budget_resets_each_window() {
+        let mut config = GlobalConfig {
+            total_staked: 1_000,
+            withdraw_window_secs: 100,
+            withdraw_window_max_amount: 300,
+            withdraw_window_max_bps: 2000, // 20% of 1000 = 200, chhota wala lagega
+            ..Default::default()
+        };
+        config.consume_withdraw_budget(150, 1_000).unwrap();
+        assert!(config.consume_withdraw_budget(51, 1_050).is_err());
+        config.consume_withdraw_budget(50, 1_099).unwrap();
+
+        // Nayi window, naya TVL snapshot
+        config.total_staked = 500;
+        assert!(config.consume_withdraw_budget(101, 1_100).is_err());
+        config.consume_withdraw_budget(100, 1_100).unwrap();
+        assert_eq!(config.withdraw_window_start, 1_100);
+    }
+
+    #[test]
+    fn distributor_proof_and_bitmap() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let leaf_a = distribution_leaf(0, alice, 100);
+        let leaf_b = distribution_leaf(1, bob, 250);
+        let root = if leaf_a <= leaf_b {
+            keccak::hashv(&[&leaf_a, &leaf_b]).0
+        } else {
+            keccak::hashv(&[&leaf_b, &leaf_a]).0
+        };
+        assert!(verify_merkle_proof(&[leaf_b], root, leaf_a));
+        assert!(verify_merkle_proof(&[leaf_a], root, leaf_b));
+        // Amount ya index badla to proof fail
+        assert!(!verify_merkle_proof(&[leaf_b], root, distribution_leaf(0, alice, 101)));
+        assert!(!verify_merkle_proof(&[leaf_a], root, distribution_leaf(2, bob, 250)));
+
+        let mut distributor = MerkleDistributor {
+            mint: Pubkey::default(),
+            round: 1,
+            merkle_root: root,
+            total_amount: 350,
+            claimed_amount: 0,
+            num_claimed: 0,
+            expiry_ts: 0,
+            bump: 0,
+            claimed_bitmap: [0u8; MAX_DISTRIBUTOR_CLAIMS / 8],
+            outstanding_amount: 350,
+        };
+        let last = (MAX_DISTRIBUTOR_CLAIMS - 1) as u64;
+        assert!(!distributor.is_claimed(9) && !distributor.is_claimed(last));
+        distributor.set_claimed(9);
+        distributor.set_claimed(last);
+        assert!(distributor.is_claimed(9) && distributor.is_claimed(last));
+        assert!(!distributor.is_claimed(8) && !distributor.is_claimed(10));
+    }
+
+    #[test]
+    fn emission_is_split_at_segment_boundaries() {
+        let segments = [
+            EmissionSegment { start_ts: 100, rate: 10 },
+            EmissionSegment { start_ts: 200, rate: 4 },
+        ];
+        assert_eq!(emitted_between(&segments, 0, 0, 100), Some(0)); // Schedule shuru hone se pehle
+        assert_eq!(emitted_between(&segments, 0, 50, 150), Some(500));
+        assert_eq!(emitted_between(&segments, 0, 150, 250), Some(50 * 10 + 50 * 4));
+        assert_eq!(emitted_between(&segments, 0, 200, 200), Some(0));
+        assert_eq!(emitted_between(&segments, 0, 250, 150), Some(0));
+        // Extreme timestamps panic nahi karte
+        let extreme = [EmissionSegment { start_ts: i64::MIN, rate: 1 }];
+        assert_eq!(emitted_between(&extreme, 10, 0, 100), None);
+        assert!(validate_emission_schedule(&extreme, 0).is_err());
+        assert!(validate_emission_schedule(&segments, 0).is_ok());
+        assert_eq!(
+            emitted_between(&segments, 0, 100, 300),
+            Some(emitted_between(&segments, 0, 100, 200).unwrap() + emitted_between(&segments, 0, 200, 300).unwrap())
+        );
+    }
+
+    #[test]
+    fn emission_halves_each_epoch_and_restarts_per_segment() {
+        let segments = [EmissionSegment { start_ts: 0, rate: 1000 }];
+        assert_eq!(emitted_between(&segments, 10, 0, 10), Some(10_000));
+        assert_eq!(emitted_between(&segments, 10, 0, 20), Some(10_000 + 5_000));
+        assert_eq!(emitted_between(&segments, 10, 5, 15), Some(5 * 1000 + 5 * 500));
+        // 1000 >> 10 = 0, uske baad kuch emit nahi hota
+        let tail: u128 = (0..10).map(|epoch| 10 * (1000u128 >> epoch)).sum();
+        assert_eq!(emitted_between(&segments, 10, 0, i64::MAX), Some(tail));
+
+        // Naye segment se halving ki ginti dobara shuru
+        let segments = [
+            EmissionSegment { start_ts: 0, rate: 800 },
+            EmissionSegment { start_ts: 20, rate: 800 },
+        ];
+        let full = emitted_between(&segments, 10, 0, 40).unwrap();
+        assert_eq!(full, 2 * (10 * 800 + 10 * 400));
+        for t in [1, 9, 10, 11, 19, 20, 21, 30, 39] {
+            let split = emitted_between(&segments, 10, 0, t).unwrap() + emitted_between(&segments, 10, t, 40).unwrap();
+            assert_eq!(split, full, "split at {}", t);
+        }
+    }
+
+    #[test]
+    fn large_position_settles_after_a_dust_staker_inflated_the_accumulator() {
+        // 1 token/s (9 decimals) ek din tak sirf weight 1 wale staker ke liye
+        let mut config = GlobalConfig {
+            emission_segments: vec![EmissionSegment { start_ts: 0, rate: 1_000_000_000 }],
+            total_weight: 1,
+            ..Default::default()
+        };
+        config.accrue_rewards(86_400).unwrap();
+
+        // 10k tokens ki position: weight x accumulator u128 se bada hai
+        let mut whale = UserStakeInfo { amount: 10_000_000_000_000, ..Default::default() };
+        assert!((whale.amount as u128).checked_mul(config.acc_reward_per_share).is_none());
+        whale.sync_reward_debt(config.acc_reward_per_share).unwrap();
+        whale.settle_rewards(config.acc_reward_per_share).unwrap();
+        assert_eq!(whale.pending_rewards, 0);
+
+        let mut dust = UserStakeInfo { amount: 1, ..Default::default() };
+        dust.settle_rewards(config.acc_reward_per_share).unwrap();
+        assert_eq!(dust.pending_rewards, 86_400 * 1_000_000_000);
+    }
+
+    #[test]
+    fn old_layouts_decode_after_zero_extension() {
+        // Upgrade se pehle: GlobalConfig { admin, withdraw_fee_bps }, UserStakeInfo { amount, deposit_ts }
+        let admin = Pubkey::new_unique();
+        let mut data = GlobalConfig::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(admin.as_ref());
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data.resize(8 + GlobalConfig::INIT_SPACE, 0);
+        let config = GlobalConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.withdraw_fee_bps, 100);
+        assert_eq!(config.fee_vault, Pubkey::default());
+        assert!(config.emission_segments.is_empty() && config.boost_tiers.is_empty());
+        assert!(config.fee_recipients.is_empty() && config.loyalty_tiers.is_empty());
+
+        let mut data = UserStakeInfo::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.resize(8 + UserStakeInfo::INIT_SPACE, 0);
+        let info = UserStakeInfo::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(info.amount, 1_000);
+        assert_eq!(info.deposit_ts, 1_700_000_000);
+        assert_eq!(info.reward_debt, 0);
+        assert_eq!(info.position_mint, Pubkey::default());
+    }
+
+    // Metadata v1 jaisa account data (padded strings, ek creator, edition_nonce aur token_standard ke saath)
+    fn metadata_blob(mint: &Pubkey, collection: Option<(bool, Pubkey)>) -> Vec<u8> {
+        let mut data = vec![METADATA_KEY_V1];
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(mint.as_ref());
+        for (text, padded) in [("Partner #1", 32usize), ("PRT", 10), ("https://x.y/1.json", 200)] {
+            data.extend_from_slice(&(padded as u32).to_le_bytes());
+            let mut bytes = text.as_bytes().to_vec();
+            bytes.resize(padded, 0);
+            data.extend_from_slice(&bytes);
+        }
+        data.extend_from_slice(&500u16.to_le_bytes());
+        data.extend_from_slice(&[1, 1, 0, 0, 0]); // Some(vec![creator])
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&[1, 100]);
+        data.extend_from_slice(&[1, 1]); // primary_sale_happened, is_mutable
+        data.extend_from_slice(&[1, 255, 1, 0]); // edition_nonce, token_standard
+        match collection {
+            Some((verified, key)) => {
+                data.extend_from_slice(&[1, verified as u8]);
+                data.extend_from_slice(key.as_ref());
+            }
+            None => data.push(0),
+        }
+        data.extend_from_slice(&[0; 16]); // Baaki fields (uses, collection_details, ...) ignore hote hain
+        data
+    }
+
+    #[test]
+    fn collection_is_read_only_when_verified_for_the_same_mint() {
+        let mint = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        assert_eq!(parse_verified_collection(&metadata_blob(&mint, Some((true, collection))), &mint), Some(collection));
+        assert_eq!(parse_verified_collection(&metadata_blob(&mint, Some((false, collection))), &mint), None);
+        assert_eq!(parse_verified_collection(&metadata_blob(&mint, None), &mint), None);
+        assert_eq!(parse_verified_collection(&metadata_blob(&mint, Some((true, collection))), &Pubkey::new_unique()), None);
+
+        let truncated = metadata_blob(&mint, Some((true, collection)));
+        assert_eq!(parse_verified_collection(&truncated[..100], &mint), None);
+    }
+
+    #[test]
+    fn pool_total_overflow_is_an_error() {
+        let mut config = GlobalConfig { total_staked: u64::MAX - 1, ..Default::default() };
+        config.add_stake(0, 1).unwrap();
+        assert_eq!(config.total_staked, u64::MAX);
+        assert!(config.add_stake(1, 1).is_err());
+        assert!(config.remove_stake(0, u64::MAX).is_ok());
+        assert!(config.remove_stake(0, 1).is_err());
+    }
+
+    #[test]
+    fn boosted_weight_overflow_is_an_error() {
+        let stake_info = UserStakeInfo { amount: u64::MAX, boost_bps: 15000, ..Default::default() };
+        assert!(stake_info.weight().is_err());
+
+        let stake_info = UserStakeInfo { amount: u64::MAX / 2, boost_bps: 20000, ..Default::default() };
+        assert_eq!(stake_info.weight().unwrap(), u64::MAX - 1);
+    }
 }
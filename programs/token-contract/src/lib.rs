@@ -1,9 +1,21 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
 use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo, Transfer};
 
+pub mod math;
+
 declare_id!("UY89vX8nRLbuy8LZCZy9ThMbNp1669Bi7Ue9uMwZC6P");
 
+/// Max number of wallets the transfer tax can be split across.
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+
+/// Returns true if `blocklist_entry` is a live `BlockedWallet` PDA created by this program.
+/// Callers derive the account from `[b"blocked", wallet]`, so an empty account means "not blocked".
+pub fn is_blocked(blocklist_entry: &AccountInfo) -> bool {
+    blocklist_entry.owner == &ID && !blocklist_entry.data_is_empty()
+}
+
 #[program]
 pub mod token_contract {
     use super::*;
@@ -22,12 +34,56 @@ pub mod token_contract {
         let config = &mut ctx.accounts.config;
         config.authority = ctx.accounts.authority.key();
         config.tax_wallet = ctx.accounts.tax_wallet.key();
+        config.compliance_authority = ctx.accounts.authority.key();
         config.tax_basis_points = tax_basis_points;
         config.bump = ctx.bumps.config;
         msg!("Tax config initialized with rate: {} basis points", tax_basis_points);
         Ok(())
     }
 
+    /// Grows a config created before `fee_recipients` and `compliance_authority` existed to the
+    /// current layout. The new bytes start zeroed (no fee split) and the compliance authority
+    /// starts as `authority`. Calling it on an up-to-date config does nothing.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        {
+            let data = config_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && data[..8] == TokenConfig::DISCRIMINATOR,
+                anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+            );
+            let authority = Pubkey::try_from(&data[8..40]).unwrap();
+            require_keys_eq!(authority, ctx.accounts.authority.key(), anchor_lang::error::ErrorCode::ConstraintHasOne);
+        }
+        if config_info.data_len() >= TokenConfig::SPACE {
+            msg!("Config already uses the current layout");
+            return Ok(());
+        }
+
+        let top_up = Rent::get()?
+            .minimum_balance(TokenConfig::SPACE)
+            .saturating_sub(config_info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: config_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        config_info.realloc(TokenConfig::SPACE, true)?;
+
+        let mut config = TokenConfig::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+        config.compliance_authority = config.authority;
+        config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+        msg!("Config migrated to {} bytes", TokenConfig::SPACE);
+        Ok(())
+    }
+
     pub fn update_config(ctx: Context<UpdateConfig>, new_tax_basis_points: u16) -> Result<()> {
         let config = &mut ctx.accounts.config;
         config.tax_basis_points = new_tax_basis_points;
@@ -35,6 +91,47 @@ pub mod token_contract {
         Ok(())
     }
 
+    pub fn set_compliance_authority(ctx: Context<UpdateConfig>, new_compliance_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.compliance_authority = new_compliance_authority;
+        msg!("Compliance authority set to: {:?}", new_compliance_authority);
+        Ok(())
+    }
+
+    /// Splits the transfer tax across `recipients` instead of `tax_wallet`.
+    /// Weights must sum to 10000 bps; an empty list sends the whole tax to `tax_wallet` again.
+    pub fn set_fee_split(ctx: Context<UpdateConfig>, recipients: Vec<FeeRecipient>) -> Result<()> {
+        validate_fee_split(&recipients)?;
+        let config = &mut ctx.accounts.config;
+        config.fee_recipients = recipients.clone();
+        msg!("Fee split set across {} recipients", recipients.len());
+        emit!(FeeSplitUpdated { recipients });
+        Ok(())
+    }
+
+    pub fn block_wallet(ctx: Context<BlockWallet>, wallet: Pubkey) -> Result<()> {
+        let entry = &mut ctx.accounts.blocked_wallet;
+        entry.wallet = wallet;
+        entry.blocked_at = Clock::get()?.unix_timestamp;
+        entry.bump = ctx.bumps.blocked_wallet;
+        msg!("Wallet blocked: {:?}", wallet);
+        emit!(WalletBlocked {
+            wallet,
+            compliance_authority: ctx.accounts.compliance_authority.key(),
+        });
+        Ok(())
+    }
+
+    pub fn unblock_wallet(ctx: Context<UnblockWallet>) -> Result<()> {
+        let wallet = ctx.accounts.blocked_wallet.wallet;
+        msg!("Wallet unblocked: {:?}", wallet);
+        emit!(WalletUnblocked {
+            wallet,
+            compliance_authority: ctx.accounts.compliance_authority.key(),
+        });
+        Ok(())
+    }
+
     pub fn mint_token(ctx: Context<MintToken>, amount: u64) -> Result<()> {
         let cpi_accounts = MintTo {
             mint: ctx.accounts.mint.to_account_info(),
@@ -53,10 +150,26 @@ pub mod token_contract {
         Ok(())
     }
 
-    pub fn transfer_token(ctx: Context<TransferToken>, amount: u64) -> Result<()> {
-        let config = &ctx.accounts.config;
-        let tax_amount = (amount * config.tax_basis_points as u64) / 10000;
-        let receive_amount = amount - tax_amount;
+    /// Read-only: returns the exact tax split `transfer_token(amount)` would apply, via return data.
+    pub fn quote_transfer(ctx: Context<QuoteTransfer>, amount: u64) -> Result<TransferQuote> {
+        let (tax, receive_amount) = ctx.accounts.config.split_tax(amount)?;
+        Ok(TransferQuote {
+            amount,
+            tax,
+            receive_amount,
+        })
+    }
+
+    pub fn transfer_token<'info>(ctx: Context<'_, '_, '_, 'info, TransferToken<'info>>, amount: u64) -> Result<()> {
+        // Neither side of the transfer, nor a delegate signing for the sender, may be on the blocklist
+        require!(
+            !is_blocked(&ctx.accounts.from_blocklist)
+                && !is_blocked(&ctx.accounts.to_blocklist)
+                && !is_blocked(&ctx.accounts.authority_blocklist),
+            ErrorCode::WalletBlocked
+        );
+
+        let (tax_amount, receive_amount) = ctx.accounts.config.split_tax(amount)?;
 
         // 1. Transfer to Recipient
         let cpi_accounts_to = Transfer {
@@ -68,8 +181,25 @@ pub mod token_contract {
         let cpi_ctx_to = CpiContext::new(cpi_program.clone(), cpi_accounts_to);
         token::transfer(cpi_ctx_to, receive_amount)?;
 
-        // 2. Transfer Tax to Tax Wallet
-        if tax_amount > 0 {
+        // 2. Transfer Tax to the fee split recipients (remaining_accounts), or the Tax Wallet
+        if tax_amount > 0 && !ctx.accounts.config.fee_recipients.is_empty() {
+            let recipients = &ctx.accounts.config.fee_recipients;
+            require!(ctx.remaining_accounts.len() >= recipients.len(), ErrorCode::FeeRecipientsMissing);
+            let weights: Vec<u64> = recipients.iter().map(|r| r.bps as u64).collect();
+            let shares = math::split_by_weights(tax_amount, &weights).ok_or(error!(ErrorCode::MathOverflow))?;
+            for ((recipient, account), share) in recipients.iter().zip(ctx.remaining_accounts).zip(shares) {
+                require_keys_eq!(account.key(), recipient.token_account, ErrorCode::FeeRecipientMismatch);
+                if share == 0 {
+                    continue;
+                }
+                let cpi_accounts_tax = Transfer {
+                    from: ctx.accounts.from.to_account_info(),
+                    to: account.clone(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                };
+                token::transfer(CpiContext::new(cpi_program.clone(), cpi_accounts_tax), share)?;
+            }
+        } else if tax_amount > 0 {
             let cpi_accounts_tax = Transfer {
                 from: ctx.accounts.from.to_account_info(),
                 to: ctx.accounts.tax_wallet.to_account_info(),
@@ -113,7 +243,7 @@ pub struct InitializeConfig<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 2 + 1, // Discriminator + Pubkey + Pubkey + u16 + u8
+        space = TokenConfig::SPACE,
         seeds = [b"config"],
         bump
     )]
@@ -124,6 +254,17 @@ pub struct InitializeConfig<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: An old-layout config does not deserialize as `TokenConfig`, so the discriminator
+    /// and authority are checked in the handler.
+    #[account(mut, seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
     #[account(
@@ -136,6 +277,47 @@ pub struct UpdateConfig<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlockWallet<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = compliance_authority,
+    )]
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init,
+        payer = compliance_authority,
+        space = 8 + 32 + 8 + 1, // Discriminator + Pubkey + i64 + u8
+        seeds = [b"blocked", wallet.as_ref()],
+        bump
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnblockWallet<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = compliance_authority,
+    )]
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        mut,
+        seeds = [b"blocked", blocked_wallet.wallet.as_ref()],
+        bump = blocked_wallet.bump,
+        close = compliance_authority,
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct MintToken<'info> {
     #[account(mut)]
@@ -147,6 +329,15 @@ pub struct MintToken<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct QuoteTransfer<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, TokenConfig>,
+}
+
 #[derive(Accounts)]
 pub struct TransferToken<'info> {
     #[account(mut)]
@@ -161,17 +352,79 @@ pub struct TransferToken<'info> {
         has_one = tax_wallet,
     )]
     pub config: Account<'info, TokenConfig>,
+    /// CHECK: Blocklist PDA of the sender's wallet; only its existence is checked.
+    #[account(seeds = [b"blocked", from.owner.as_ref()], bump)]
+    pub from_blocklist: UncheckedAccount<'info>,
+    /// CHECK: Blocklist PDA of the receiver's wallet; only its existence is checked.
+    #[account(seeds = [b"blocked", to.owner.as_ref()], bump)]
+    pub to_blocklist: UncheckedAccount<'info>,
+    /// CHECK: Blocklist PDA of the signer (the owner or a delegate); only its existence is checked.
+    #[account(seeds = [b"blocked", authority.key().as_ref()], bump)]
+    pub authority_blocklist: UncheckedAccount<'info>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
 
+// New fields go at the end; configs created with an older layout are grown by `migrate_config`.
 #[account]
 pub struct TokenConfig {
     pub authority: Pubkey,
     pub tax_wallet: Pubkey,
     pub tax_basis_points: u16,
     pub bump: u8,
+    pub fee_recipients: Vec<FeeRecipient>, // Empty = whole tax goes to tax_wallet
+    pub compliance_authority: Pubkey, // Manages the wallet blocklist
+}
+
+impl TokenConfig {
+    // Discriminator + Pubkey + Pubkey + u16 + u8 + Vec<FeeRecipient> + Pubkey
+    pub const SPACE: usize = 8 + 32 + 32 + 2 + 1 + 4 + MAX_FEE_RECIPIENTS * (32 + 2) + 32;
+
+    /// Splits `amount` into (tax, amount received by the recipient). Tax rounds down.
+    pub fn split_tax(&self, amount: u64) -> Result<(u64, u64)> {
+        math::split_bps(amount, self.tax_basis_points as u64).ok_or(error!(ErrorCode::MathOverflow))
+    }
+}
+
+#[account]
+pub struct BlockedWallet {
+    pub wallet: Pubkey,
+    pub blocked_at: i64,
+    pub bump: u8,
+}
+
+/// One share of a fee split. Also used by the staking program for its withdraw fee split.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeRecipient {
+    pub token_account: Pubkey, // Token account that receives this share of the tax
+    pub bps: u16,              // Share of the tax; all recipients sum to 10000
+}
+
+/// Returns true if `recipients` is empty (no split) or has 1-5 unique accounts with
+/// non-zero weights summing to 10000 bps.
+pub fn is_valid_fee_split(recipients: &[FeeRecipient]) -> bool {
+    if recipients.is_empty() {
+        return true;
+    }
+    let total: u64 = recipients.iter().map(|r| r.bps as u64).sum();
+    recipients.len() <= MAX_FEE_RECIPIENTS
+        && total == math::BPS_DENOMINATOR
+        && recipients.iter().enumerate().all(|(i, recipient)| {
+            recipient.bps > 0 && recipients[..i].iter().all(|r| r.token_account != recipient.token_account)
+        })
+}
+
+fn validate_fee_split(recipients: &[FeeRecipient]) -> Result<()> {
+    require!(is_valid_fee_split(recipients), ErrorCode::InvalidFeeSplit);
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferQuote {
+    pub amount: u64,
+    pub tax: u64,
+    pub receive_amount: u64,
 }
 
 #[event]
@@ -197,3 +450,75 @@ pub struct TokensTransferred {
     pub authority: Pubkey,
 
 }
+
+#[event]
+pub struct FeeSplitUpdated {
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[event]
+pub struct WalletBlocked {
+    pub wallet: Pubkey,
+    pub compliance_authority: Pubkey,
+}
+
+#[event]
+pub struct WalletUnblocked {
+    pub wallet: Pubkey,
+    pub compliance_authority: Pubkey,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Wallet is on the compliance blocklist")]
+    WalletBlocked,
+    #[msg("Arithmetic overflow or invalid basis points")]
+    MathOverflow,
+    #[msg("Fee split needs 1-5 unique recipients with non-zero weights summing to 10000 bps")]
+    InvalidFeeSplit,
+    #[msg("Fee split recipient accounts must be passed as remaining accounts")]
+    FeeRecipientsMissing,
+    #[msg("Remaining account does not match the configured fee recipient")]
+    FeeRecipientMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_config_layout_decodes_after_zero_extension() {
+        // Layout before fee_recipients / compliance_authority: authority, tax_wallet, tax_basis_points, bump
+        let authority = Pubkey::new_unique();
+        let tax_wallet = Pubkey::new_unique();
+        let mut data = TokenConfig::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(tax_wallet.as_ref());
+        data.extend_from_slice(&250u16.to_le_bytes());
+        data.push(254);
+        data.resize(TokenConfig::SPACE, 0);
+
+        let config = TokenConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(config.authority, authority);
+        assert_eq!(config.tax_wallet, tax_wallet);
+        assert_eq!(config.tax_basis_points, 250);
+        assert_eq!(config.bump, 254);
+        assert!(config.fee_recipients.is_empty());
+        assert_eq!(config.compliance_authority, Pubkey::default());
+    }
+
+    #[test]
+    fn full_config_fits_in_space() {
+        let config = TokenConfig {
+            authority: Pubkey::new_unique(),
+            tax_wallet: Pubkey::new_unique(),
+            tax_basis_points: 100,
+            bump: 255,
+            fee_recipients: vec![FeeRecipient { token_account: Pubkey::new_unique(), bps: 2000 }; MAX_FEE_RECIPIENTS],
+            compliance_authority: Pubkey::new_unique(),
+        };
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), TokenConfig::SPACE);
+    }
+}
@@ -0,0 +1,163 @@
+//! Checked token math shared by `token_contract` and `staking_contract`.
+//!
+//! Every helper widens to `u128` for the intermediate product and returns `None` instead of
+//! panicking or wrapping, so callers can map it to their program's `MathOverflow` error.
+//!
+//! Rounding: divisions round DOWN (toward zero). A fee or tax computed here is never larger
+//! than its exact value, so the leftover dust always stays with the payer/user side.
+
+/// Basis point denominator (100% = 10_000 bps).
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// `floor(a * b / denominator)`. `None` if `denominator` is zero or the result does not fit in `u64`.
+pub fn mul_div_floor(a: u64, b: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    let result = (a as u128).checked_mul(b as u128)? / denominator as u128;
+    u64::try_from(result).ok()
+}
+
+/// `floor(a * b / denominator)` for `u128` operands, with a 256-bit intermediate product so
+/// `a * b` itself may exceed `u128`. `None` if `denominator` is zero or the result does not fit in `u128`.
+pub fn mul_div_floor_u128(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+    if let Some(product) = a.checked_mul(b) {
+        return Some(product / denominator);
+    }
+
+    // 256-bit product as (hi, lo) from 64-bit limbs
+    const LOW: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & LOW);
+    let (b1, b0) = (b >> 64, b & LOW);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    let mid = (p00 >> 64) + (p01 & LOW) + (p10 & LOW);
+    let lo = (p00 & LOW) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    if hi >= denominator {
+        return None;
+    }
+
+    // Long division of (hi, lo) by denominator; the remainder always stays below it
+    let mut rem = hi;
+    let mut quotient: u128 = 0;
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || rem >= denominator {
+            rem = rem.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+/// `floor(amount * bps / 10_000)`.
+pub fn bps_of(amount: u64, bps: u64) -> Option<u64> {
+    mul_div_floor(amount, bps, BPS_DENOMINATOR)
+}
+
+/// Splits `amount` into `(cut, rest)` with `cut = bps_of(amount, bps)` and `cut + rest == amount`.
+/// `None` if `bps` is above 100%.
+pub fn split_bps(amount: u64, bps: u64) -> Option<(u64, u64)> {
+    if bps > BPS_DENOMINATOR {
+        return None;
+    }
+    let cut = bps_of(amount, bps)?;
+    Some((cut, amount.checked_sub(cut)?))
+}
+
+/// Splits `amount` across recipients by bps `weights`, which must sum to exactly 100%.
+/// Every share rounds down and the last recipient takes the dust, so the parts always add up
+/// to `amount`. `None` for an empty list or a bad weight sum.
+pub fn split_by_weights(amount: u64, weights: &[u64]) -> Option<Vec<u64>> {
+    let total = weights.iter().try_fold(0u64, |acc, w| acc.checked_add(*w))?;
+    if weights.is_empty() || total != BPS_DENOMINATOR {
+        return None;
+    }
+    let mut parts = Vec::with_capacity(weights.len());
+    let mut left = amount;
+    for w in &weights[..weights.len() - 1] {
+        let part = bps_of(amount, *w)?;
+        left = left.checked_sub(part)?;
+        parts.push(part);
+    }
+    parts.push(left);
+    Some(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bps_of_does_not_overflow_near_u64_max() {
+        // u64::MAX * 10_000 overflows u64 but not u128
+        assert_eq!(bps_of(u64::MAX, BPS_DENOMINATOR), Some(u64::MAX));
+        assert_eq!(bps_of(u64::MAX, 5_000), Some(u64::MAX / 2));
+        assert_eq!(bps_of(u64::MAX, 0), Some(0));
+    }
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(50, 300), Some(1)); // 1.5 -> 1
+        assert_eq!(bps_of(9_999, 1), Some(0)); // 0.9999 -> 0
+        assert_eq!(bps_of(10_000, 1), Some(1));
+    }
+
+    #[test]
+    fn mul_div_floor_rejects_results_above_u64() {
+        assert_eq!(mul_div_floor(u64::MAX, 2, 1), None);
+        assert_eq!(mul_div_floor(u64::MAX, u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(mul_div_floor(1, 1, 0), None);
+    }
+
+    #[test]
+    fn mul_div_floor_u128_handles_products_above_u128() {
+        assert_eq!(mul_div_floor_u128(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div_floor_u128(u128::MAX, 2, 4), Some(u128::MAX / 2));
+        assert_eq!(mul_div_floor_u128(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+        assert_eq!(mul_div_floor_u128(u64::MAX as u128, 10_000_000_000_000_000_000_000_000, 1_000_000_000_000), Some(184_467_440_737_095_516_150_000_000_000_000));
+        assert_eq!(mul_div_floor_u128(7, 3, 2), Some(10)); // 10.5 -> 10
+        assert_eq!(mul_div_floor_u128(u128::MAX, u128::MAX, 1), None);
+        assert_eq!(mul_div_floor_u128(1, 1, 0), None);
+    }
+
+    #[test]
+    fn split_bps_parts_always_add_up() {
+        for amount in [0, 1, 99, 10_001, u64::MAX - 1, u64::MAX] {
+            for bps in [0, 1, 250, 9_999, BPS_DENOMINATOR] {
+                let (cut, rest) = split_bps(amount, bps).unwrap();
+                assert_eq!(cut as u128 + rest as u128, amount as u128);
+                assert!(cut <= amount);
+            }
+        }
+    }
+
+    #[test]
+    fn split_bps_rejects_more_than_100_percent() {
+        assert_eq!(split_bps(u64::MAX, BPS_DENOMINATOR + 1), None);
+        assert_eq!(split_bps(1, u64::MAX), None);
+    }
+
+    #[test]
+    fn split_by_weights_gives_dust_to_last_recipient() {
+        assert_eq!(split_by_weights(101, &[5_000, 5_000]), Some(vec![50, 51]));
+        assert_eq!(split_by_weights(10, &[3_333, 3_333, 3_334]), Some(vec![3, 3, 4]));
+        let parts = split_by_weights(u64::MAX, &[1, 9_999]).unwrap();
+        assert_eq!(parts.iter().map(|p| *p as u128).sum::<u128>(), u64::MAX as u128);
+    }
+
+    #[test]
+    fn split_by_weights_requires_exactly_100_percent() {
+        assert_eq!(split_by_weights(100, &[]), None);
+        assert_eq!(split_by_weights(100, &[5_000, 4_999]), None);
+        assert_eq!(split_by_weights(100, &[u64::MAX, 1]), None);
+    }
+}
@@ -0,0 +1,255 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { StakingContract } from "../../target/types/staking_contract";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+// Reward mint == staked mint wala pool. Program me ek hi pool (b"config") hai, isliye
+// ye suite apne Test.toml se `anchor test` me alag fresh validator pe chalta hai.
+describe("staking_contract_compound", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.StakingContract as Program<StakingContract>;
+  const admin = provider.wallet as anchor.Wallet;
+  const userB = anchor.web3.Keypair.generate();
+
+  const BOUNTY_BPS = 500; // MAX_COMPOUND_BOUNTY_BPS
+
+  let mint: anchor.web3.PublicKey;
+  let vault: anchor.web3.PublicKey;
+  let rewardVault: anchor.web3.PublicKey;
+  let adminTokenAccount: anchor.web3.PublicKey;
+  let userBTokenAccount: anchor.web3.PublicKey;
+
+  const [config] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")], program.programId
+  );
+  const stakeInfoFor = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), wallet.toBuffer()], program.programId
+    )[0];
+  const tokenContractId = new anchor.web3.PublicKey("UY89vX8nRLbuy8LZCZy9ThMbNp1669Bi7Ue9uMwZC6P");
+  const blocklistPda = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("blocked"), wallet.toBuffer()], tokenContractId
+    )[0];
+
+  const balance = async (account: anchor.web3.PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  const deposit = (staker: anchor.web3.Keypair, stakerTokenAccount: anchor.web3.PublicKey, amount: number) =>
+    program.methods.deposit(new anchor.BN(amount)).accounts({
+      staker: staker.publicKey,
+      vault: vault,
+      stakeInfo: stakeInfoFor(staker.publicKey),
+      mint: mint,
+      stakerTokenAccount: stakerTokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(staker.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).signers([staker]).rpc();
+
+  const compoundAdmin = () =>
+    program.methods.compound().accounts({
+      staker: admin.publicKey,
+      stakeInfo: stakeInfoFor(admin.publicKey),
+      config: config,
+      vault: vault,
+      rewardVault: rewardVault,
+      mint: mint,
+      boostAccount: null,
+      stakerBlocklist: blocklistPda(admin.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    }).rpc();
+
+  const setCaps = (maxTotalStaked: anchor.BN, maxPerUser: anchor.BN) =>
+    program.methods.updateDepositCaps(maxTotalStaked, maxPerUser).accounts({
+      config: config,
+      admin: admin.publicKey
+    }).rpc();
+
+  const crankB = () =>
+    program.methods.crankCompound().accounts({
+      cranker: admin.publicKey,
+      owner: userB.publicKey,
+      stakeInfo: stakeInfoFor(userB.publicKey),
+      config: config,
+      vault: vault,
+      rewardVault: rewardVault,
+      mint: mint,
+      crankerTokenAccount: adminTokenAccount,
+      boostAccount: null,
+      ownerBlocklist: blocklistPda(userB.publicKey),
+      crankerBlocklist: blocklistPda(admin.publicKey),
+      recipientBlocklist: blocklistPda(admin.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    }).rpc();
+
+  it("Setup: Pool whose reward mint is the staked mint", async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(userB.publicKey, 1000000000)
+    );
+
+    mint = await createMint(provider.connection, admin.payer, admin.publicKey, null, 6);
+    [vault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer()], program.programId
+    );
+    [rewardVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_vault"), mint.toBuffer()], program.programId
+    );
+    adminTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin.payer, mint, admin.publicKey
+    )).address;
+    userBTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin.payer, mint, userB.publicKey
+    )).address;
+    await mintTo(provider.connection, admin.payer, mint, adminTokenAccount, admin.publicKey, 10_000);
+    await mintTo(provider.connection, admin.payer, mint, userBTokenAccount, admin.publicKey, 10_000);
+    const feeVault = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin.payer, mint, anchor.web3.Keypair.generate().publicKey
+    )).address;
+
+    await program.methods.initialize(new anchor.BN(0)).accounts({
+      vault: vault,
+      config: config,
+      mint: mint,
+      feeVault: feeVault,
+      payer: admin.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc();
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods.initializeRewards(
+      [{ startTs: new anchor.BN(now - 10), rate: new anchor.BN(100) }],
+      new anchor.BN(0)
+    ).accounts({
+      config: config,
+      rewardVault: rewardVault,
+      rewardMint: mint,
+      admin: admin.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc();
+    await mintTo(provider.connection, admin.payer, mint, rewardVault, admin.publicKey, 1_000_000);
+
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.rewardMint.toBase58(), mint.toBase58());
+  });
+
+  it("POSITIVE: compound moves pending rewards into the stake without leaving the program", async () => {
+    await deposit(admin.payer, adminTokenAccount, 1000);
+    await new Promise((r) => setTimeout(r, 2000));
+
+    const walletBefore = await balance(adminTokenAccount);
+    const rewardVaultBefore = await balance(rewardVault);
+    const vaultBefore = await balance(vault);
+
+    await compoundAdmin();
+
+    const info = await program.account.userStakeInfo.fetch(stakeInfoFor(admin.publicKey));
+    const compounded = info.amount.toNumber() - 1000;
+    assert.isAbove(compounded, 0);
+    assert.equal(info.pendingRewards.toNumber(), 0);
+
+    // Reward vault -> staking vault, wallet ko kuch nahi gaya
+    assert.equal(await balance(adminTokenAccount), walletBefore);
+    assert.equal(rewardVaultBefore - await balance(rewardVault), compounded);
+    assert.equal(await balance(vault) - vaultBefore, compounded);
+
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.totalStaked.toNumber(), info.amount.toNumber());
+  });
+
+  it("NEGATIVE: compound cannot grow a position past the deposit caps", async () => {
+    await new Promise((r) => setTimeout(r, 2000));
+    const info = await program.account.userStakeInfo.fetch(stakeInfoFor(admin.publicKey));
+    const cfg = await program.account.globalConfig.fetch(config);
+
+    // Position pehle se per-user cap pe hai
+    await setCaps(new anchor.BN(0), info.amount);
+    try {
+      await compoundAdmin();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "UserCapExceeded");
+    }
+
+    // Pool pehle se total cap pe hai
+    await setCaps(cfg.totalStaked, new anchor.BN(0));
+    try {
+      await compoundAdmin();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "PoolCapExceeded");
+    }
+
+    await setCaps(new anchor.BN(0), new anchor.BN(0));
+    const after = await program.account.userStakeInfo.fetch(stakeInfoFor(admin.publicKey));
+    assert.isTrue(after.amount.eq(info.amount));
+  });
+
+  it("NEGATIVE: crank_compound needs the owner's opt-in", async () => {
+    await deposit(userB, userBTokenAccount, 1000);
+    await new Promise((r) => setTimeout(r, 2000));
+    try {
+      await crankB();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "AutoCompoundDisabled");
+    }
+  });
+
+  it("POSITIVE: crank_compound pays the cranker its bounty and compounds the rest", async () => {
+    await program.methods.updateCompoundBounty(new anchor.BN(BOUNTY_BPS)).accounts({
+      config: config,
+      admin: admin.publicKey
+    }).rpc();
+    await program.methods.setAutoCompound(true).accounts({
+      staker: userB.publicKey,
+      stakeInfo: stakeInfoFor(userB.publicKey),
+    }).signers([userB]).rpc();
+
+    const crankerBefore = await balance(adminTokenAccount);
+    const rewardVaultBefore = await balance(rewardVault);
+    const before = await program.account.userStakeInfo.fetch(stakeInfoFor(userB.publicKey));
+
+    await crankB();
+
+    const after = await program.account.userStakeInfo.fetch(stakeInfoFor(userB.publicKey));
+    const bounty = await balance(adminTokenAccount) - crankerBefore;
+    const compounded = after.amount.toNumber() - before.amount.toNumber();
+    const reward = rewardVaultBefore - await balance(rewardVault);
+
+    assert.isAbove(compounded, 0);
+    assert.equal(bounty + compounded, reward);
+    assert.equal(bounty, Math.floor(reward * BOUNTY_BPS / 10_000)); // Bounty neeche round hoti hai
+    assert.equal(after.pendingRewards.toNumber(), 0);
+    assert.equal(await balance(userBTokenAccount), 9_000); // Owner ke wallet me kuch nahi aaya
+  });
+
+  it("NEGATIVE: crank_compound respects the pool cap", async () => {
+    await new Promise((r) => setTimeout(r, 2000));
+    const cfg = await program.account.globalConfig.fetch(config);
+    await setCaps(cfg.totalStaked, new anchor.BN(0));
+    try {
+      await crankB();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "PoolCapExceeded");
+    }
+    await setCaps(new anchor.BN(0), new anchor.BN(0));
+  });
+});
@@ -0,0 +1,157 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { StakingContract } from "../../target/types/staking_contract";
+import { createAccount, getAccount, getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+import { assert } from "chai";
+
+// Validator Test.toml ke fixtures ke saath chalta hai: upgrade se pehle wale layout ka config
+// (admin + withdraw_fee_bps = 100) aur admin ka ledger (amount = 1000, deposit_ts = 1_700_000_000),
+// saath me pool ka mint aur 1000 tokens wala vault.
+describe("staking_contract_layout_migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.StakingContract as Program<StakingContract>;
+  const admin = provider.wallet as anchor.Wallet;
+  const hacker = anchor.web3.Keypair.generate();
+
+  const mint = new anchor.web3.PublicKey("6maihGexDrRX3YZUZx8srnQd7iYBuRiGmfyxNibMYkFp");
+  const [config] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")], program.programId
+  );
+  const [vault] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), mint.toBuffer()], program.programId
+  );
+  const [stakeInfo] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("user"), admin.publicKey.toBuffer()], program.programId
+  );
+  const tokenContractId = new anchor.web3.PublicKey("UY89vX8nRLbuy8LZCZy9ThMbNp1669Bi7Ue9uMwZC6P");
+  const blocklistPda = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("blocked"), wallet.toBuffer()], tokenContractId
+    )[0];
+
+  let feeVault: anchor.web3.PublicKey;
+  let adminTokenAccount: anchor.web3.PublicKey;
+
+  const dataLen = async (account: anchor.web3.PublicKey) =>
+    (await provider.connection.getAccountInfo(account)).data.length;
+
+  const migrateConfig = (signer: anchor.web3.Keypair) =>
+    program.methods.migrateConfig().accounts({
+      config: config,
+      vault: vault,
+      mint: mint,
+      feeVault: feeVault,
+      admin: signer.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([signer]).rpc();
+
+  const migrateStakeInfo = () =>
+    program.methods.migrateStakeInfo().accounts({
+      payer: admin.publicKey,
+      owner: admin.publicKey,
+      stakeInfo: stakeInfo,
+      config: config,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc();
+
+  before(async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(hacker.publicKey, 1000000000)
+    );
+    feeVault = await createAccount(
+      provider.connection, admin.payer, mint, anchor.web3.Keypair.generate().publicKey
+    );
+    adminTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin.payer, mint, admin.publicKey
+    )).address;
+  });
+
+  it("Fixtures use the old layouts", async () => {
+    assert.equal(await dataLen(config), 8 + 32 + 8);
+    assert.equal(await dataLen(stakeInfo), 8 + 8 + 8);
+  });
+
+  it("NEGATIVE: Ledger cannot be migrated before the config", async () => {
+    try {
+      await migrateStakeInfo();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "AccountDidNotDeserialize");
+    }
+  });
+
+  it("NEGATIVE: Only the admin can migrate the config", async () => {
+    try {
+      await migrateConfig(hacker);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Unauthorized");
+    }
+  });
+
+  it("POSITIVE: Admin migrates the config and keeps the old fields", async () => {
+    await migrateConfig(admin.payer);
+
+    assert.isAbove(await dataLen(config), 8 + 32 + 8);
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.admin.toBase58(), admin.publicKey.toBase58());
+    assert.equal(cfg.withdrawFeeBps.toNumber(), 100);
+    assert.equal(cfg.feeVault.toBase58(), feeVault.toBase58());
+    // Vault ka pura balance principal hai, chahe ledger abhi migrate na hua ho
+    assert.equal(cfg.totalStaked.toNumber(), 1000);
+    assert.equal(cfg.totalWeight.toNumber(), 0);
+    assert.equal(cfg.activeStakers.toNumber(), 0);
+  });
+
+  it("POSITIVE: Ledger migration keeps amount and deposit time and adds the position's weight", async () => {
+    await migrateStakeInfo();
+
+    assert.isAbove(await dataLen(stakeInfo), 8 + 8 + 8);
+    const info = await program.account.userStakeInfo.fetch(stakeInfo);
+    assert.equal(info.amount.toNumber(), 1000);
+    assert.equal(info.depositTs.toNumber(), 1_700_000_000);
+    assert.equal(info.loyaltyStartTs.toNumber(), 1_700_000_000);
+    assert.equal(info.positionMint.toBase58(), anchor.web3.PublicKey.default.toBase58());
+
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.totalStaked.toNumber(), 1000);
+    assert.equal(cfg.totalWeight.toNumber(), 1000);
+    assert.equal(cfg.activeStakers.toNumber(), 1);
+  });
+
+  it("POSITIVE: Migrating again changes nothing", async () => {
+    const configBefore = (await provider.connection.getAccountInfo(config)).data;
+    const infoBefore = (await provider.connection.getAccountInfo(stakeInfo)).data;
+    await migrateConfig(admin.payer);
+    await migrateStakeInfo();
+    assert.ok((await provider.connection.getAccountInfo(config)).data.equals(configBefore));
+    assert.ok((await provider.connection.getAccountInfo(stakeInfo)).data.equals(infoBefore));
+  });
+
+  it("POSITIVE: A migrated position can be withdrawn", async () => {
+    await program.methods.withdraw(new anchor.BN(1000)).accounts({
+      staker: admin.publicKey,
+      vault: vault,
+      stakeInfo: stakeInfo,
+      mint: mint,
+      recipient: adminTokenAccount,
+      feeVault: feeVault,
+      config: config,
+      referralAccount: null,
+      boostAccount: null,
+      insuranceVault: null,
+      stakerBlocklist: blocklistPda(admin.publicKey),
+      recipientBlocklist: blocklistPda(admin.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).rpc();
+
+    // 1% fee
+    assert.equal(Number((await getAccount(provider.connection, adminTokenAccount)).amount), 990);
+    assert.equal(Number((await getAccount(provider.connection, feeVault)).amount), 10);
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.totalStaked.toNumber(), 0);
+    assert.equal(cfg.activeStakers.toNumber(), 0);
+  });
+});
@@ -0,0 +1,71 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TokenContract } from "../../target/types/token_contract";
+import { assert } from "chai";
+
+// The validator starts with a first-release TokenConfig (see Test.toml):
+// authority = provider wallet, tax_wallet = 6maih...MYkFp, 250 bps, bump 255.
+describe("token-contract-config-migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TokenContract as Program<TokenContract>;
+  const payer = provider.wallet as anchor.Wallet;
+
+  const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const taxWallet = new anchor.web3.PublicKey("6maihGexDrRX3YZUZx8srnQd7iYBuRiGmfyxNibMYkFp");
+
+  // Discriminator + Pubkey + Pubkey + u16 + u8
+  const OLD_SPACE = 8 + 32 + 32 + 2 + 1;
+  // ... + Vec<FeeRecipient> (5 max) + compliance_authority
+  const NEW_SPACE = OLD_SPACE + 4 + 5 * (32 + 2) + 32;
+
+  const migrate = () =>
+    program.methods
+      .migrateConfig()
+      .accounts({
+        config: configPda,
+        authority: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+  it("POSITIVE: migrate_config grows an old-layout Config and keeps its fields", async () => {
+    const before = await provider.connection.getAccountInfo(configPda);
+    assert.equal(before.data.length, OLD_SPACE);
+
+    await migrate();
+
+    const after = await provider.connection.getAccountInfo(configPda);
+    assert.equal(after.data.length, NEW_SPACE);
+    assert.ok(after.data.subarray(0, OLD_SPACE).equals(before.data), "Old bytes must not move");
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(NEW_SPACE);
+    assert.isAtLeast(after.lamports, rent);
+
+    const configAccount = await program.account.tokenConfig.fetch(configPda);
+    assert.ok(configAccount.authority.equals(payer.publicKey));
+    assert.ok(configAccount.taxWallet.equals(taxWallet));
+    assert.equal(configAccount.taxBasisPoints, 250);
+    assert.equal(configAccount.bump, 255);
+    assert.equal(configAccount.feeRecipients.length, 0);
+    assert.ok(configAccount.complianceAuthority.equals(payer.publicKey));
+  });
+
+  it("POSITIVE: A migrated Config works with the new instructions", async () => {
+    const complianceAuthority = anchor.web3.Keypair.generate().publicKey;
+    await program.methods
+      .setComplianceAuthority(complianceAuthority)
+      .accounts({ config: configPda, authority: payer.publicKey })
+      .rpc();
+    const configAccount = await program.account.tokenConfig.fetch(configPda);
+    assert.ok(configAccount.complianceAuthority.equals(complianceAuthority));
+
+    // Second migration is a no-op and does not reset the compliance authority
+    await migrate();
+    const again = await program.account.tokenConfig.fetch(configPda);
+    assert.ok(again.complianceAuthority.equals(complianceAuthority));
+  });
+});
@@ -1,12 +1,20 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { StakingContract } from "../target/types/staking_contract";
+import { TokenContract } from "../target/types/token_contract";
+import { MockPool } from "../target/types/mock_pool";
 import {
   createMint,
   getOrCreateAssociatedTokenAccount,
   mintTo,
-  getAccount
+  getAccount,
+  getMint,
+  createAccount,
+  transfer,
+  getAssociatedTokenAddressSync,
+  ASSOCIATED_TOKEN_PROGRAM_ID
 } from "@solana/spl-token";
+import { keccak_256 } from "@noble/hashes/sha3";
 import { assert } from "chai";
 
 describe("staking_contract_comprehensive_tests", () => {
@@ -35,6 +43,33 @@ describe("staking_contract_comprehensive_tests", () => {
   // Hacker (Unauthorized User)
   const hacker = anchor.web3.Keypair.generate();
 
+  // Token contract ka blocklist PDA (deposit/withdraw isko check karte hain, account khaali = blocked nahi)
+  const tokenContractId = new anchor.web3.PublicKey("UY89vX8nRLbuy8LZCZy9ThMbNp1669Bi7Ue9uMwZC6P");
+  const blocklistPda = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("blocked"), wallet.toBuffer()], tokenContractId
+    )[0];
+
+  // Blocklist entries token_contract ki compliance authority (provider wallet) banati hai.
+  // Token config token-contract.ts me initialize hota hai, isliye wo suite pehle chalta hai (Anchor.toml).
+  const tokenProgram = anchor.workspace.TokenContract as Program<TokenContract>;
+  const [tokenConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")], tokenContractId
+  );
+  const blockWallet = (wallet: anchor.web3.PublicKey) =>
+    tokenProgram.methods.blockWallet(wallet).accounts({
+      config: tokenConfig,
+      blockedWallet: blocklistPda(wallet),
+      complianceAuthority: userA.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc();
+  const unblockWallet = (wallet: anchor.web3.PublicKey) =>
+    tokenProgram.methods.unblockWallet().accounts({
+      config: tokenConfig,
+      blockedWallet: blocklistPda(wallet),
+      complianceAuthority: userA.publicKey,
+    }).rpc();
+
   before(async () => {
     // Airdrop SOL to User B and Hacker
     await provider.connection.confirmTransaction(
@@ -106,6 +141,7 @@ describe("staking_contract_comprehensive_tests", () => {
         vault: vault,
         config: config,
         mint: mint,
+        feeVault: feeVault,
         payer: userA.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
@@ -117,6 +153,7 @@ describe("staking_contract_comprehensive_tests", () => {
     const configAccount = await program.account.globalConfig.fetch(config);
     assert.equal(configAccount.admin.toBase58(), userA.publicKey.toBase58());
     assert.equal(configAccount.withdrawFeeBps.toNumber(), 100);
+    assert.equal(configAccount.feeVault.toBase58(), feeVault.toBase58());
   });
 
   // =========================================================================
@@ -156,6 +193,11 @@ describe("staking_contract_comprehensive_tests", () => {
       stakeInfo: userAStakeInfo,
       mint: mint,
       stakerTokenAccount: userATokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(userA.publicKey),
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId
     }).rpc();
@@ -172,6 +214,11 @@ describe("staking_contract_comprehensive_tests", () => {
       stakeInfo: userBStakeInfo,
       mint: mint,
       stakerTokenAccount: userBTokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(userB.publicKey),
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId
     }).signers([userB]).rpc();
@@ -196,6 +243,11 @@ describe("staking_contract_comprehensive_tests", () => {
         stakeInfo: userAStakeInfo,
         mint: mint,
         stakerTokenAccount: userATokenAccount,
+        config: config,
+        allowlistEntry: null,
+        boostAccount: null,
+        referralAccount: null,
+        stakerBlocklist: blocklistPda(userA.publicKey),
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId
       }).rpc();
@@ -213,6 +265,11 @@ describe("staking_contract_comprehensive_tests", () => {
         stakeInfo: userAStakeInfo,
         mint: mint,
         stakerTokenAccount: userATokenAccount,
+        config: config,
+        allowlistEntry: null,
+        boostAccount: null,
+        referralAccount: null,
+        stakerBlocklist: blocklistPda(userA.publicKey),
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId
       }).rpc();
@@ -226,6 +283,29 @@ describe("staking_contract_comprehensive_tests", () => {
   // 4. WITHDRAW SCENARIOS
   // =========================================================================
 
+  it("NEGATIVE: Staker cannot route the withdraw fee to their own account", async () => {
+    try {
+      await program.methods.withdraw(new anchor.BN(100)).accounts({
+        staker: userA.publicKey,
+        vault: vault,
+        stakeInfo: userAStakeInfo,
+        mint: mint,
+        recipient: userATokenAccount,
+        feeVault: userATokenAccount, // Fee wapis apne hi account me
+        config: config,
+        boostAccount: null,
+        referralAccount: null,
+        insuranceVault: null,
+        stakerBlocklist: blocklistPda(userA.publicKey),
+        recipientBlocklist: blocklistPda(userA.publicKey),
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Fee vault does not match the pool's configured fee vault");
+    }
+  });
+
   it("POSITIVE: User A Withdraws with 5% Fee", async () => {
     // Setup: User A has 100 staked. Fee is 5%.
     // Expected Fee: 100 * 5% = 5 tokens.
@@ -234,14 +314,19 @@ describe("staking_contract_comprehensive_tests", () => {
     // Initial Balance (User A): 900 (1000 minted - 100 staked)
     // Expected Final: 900 + 95 = 995.
 
-    await program.methods.withdraw().accounts({
+    await program.methods.withdraw(new anchor.BN(100)).accounts({
       staker: userA.publicKey,
       vault: vault,
       stakeInfo: userAStakeInfo,
       mint: mint,
-      stakerTokenAccount: userATokenAccount,
+      recipient: userATokenAccount,
       feeVault: feeVault,
       config: config,
+      boostAccount: null,
+      referralAccount: null,
+      insuranceVault: null,
+      stakerBlocklist: blocklistPda(userA.publicKey),
+      recipientBlocklist: blocklistPda(userA.publicKey),
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
     }).rpc();
 
@@ -264,14 +349,19 @@ describe("staking_contract_comprehensive_tests", () => {
 
   it("NEGATIVE: Double Withdraw", async () => {
     try {
-      await program.methods.withdraw().accounts({
+      await program.methods.withdraw(new anchor.BN(100)).accounts({
         staker: userA.publicKey,
         vault: vault,
         stakeInfo: userAStakeInfo,
         mint: mint,
-        stakerTokenAccount: userATokenAccount,
+        recipient: userATokenAccount,
         feeVault: feeVault,
         config: config,
+        boostAccount: null,
+        referralAccount: null,
+        insuranceVault: null,
+        stakerBlocklist: blocklistPda(userA.publicKey),
+        recipientBlocklist: blocklistPda(userA.publicKey),
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
       }).rpc();
       assert.fail("Should fail");
@@ -290,14 +380,19 @@ describe("staking_contract_comprehensive_tests", () => {
     // Expected: Full 200 back. No Fee.
     const initialBal = (await getAccount(provider.connection, userBTokenAccount)).amount; // 800
 
-    await program.methods.withdraw().accounts({
+    await program.methods.withdraw(new anchor.BN(200)).accounts({
       staker: userB.publicKey,
       vault: vault,
       stakeInfo: userBStakeInfo,
       mint: mint,
-      stakerTokenAccount: userBTokenAccount,
+      recipient: userBTokenAccount,
       feeVault: feeVault,
       config: config,
+      boostAccount: null,
+      referralAccount: null,
+      insuranceVault: null,
+      stakerBlocklist: blocklistPda(userB.publicKey),
+      recipientBlocklist: blocklistPda(userB.publicKey),
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
     }).signers([userB]).rpc();
 
@@ -310,4 +405,1927 @@ describe("staking_contract_comprehensive_tests", () => {
     assert.equal(Number(feeAcc.amount), 5);
   });
 
+  // =========================================================================
+  // 5. REWARD EMISSION SCHEDULE
+  // =========================================================================
+
+  let rewardMint: anchor.web3.PublicKey;
+  let rewardVault: anchor.web3.PublicKey;
+  let userARewardAccount: anchor.web3.PublicKey;
+
+  it("POSITIVE: Admin commits emission schedule with halving", async () => {
+    rewardMint = await createMint(
+      provider.connection, userA.payer, userA.publicKey, null, 6
+    );
+    [rewardVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_vault"), rewardMint.toBuffer()], program.programId
+    );
+    const rewardAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection, userA.payer, rewardMint, userA.publicKey
+    );
+    userARewardAccount = rewardAta.address;
+
+    // Segment 1: abhi se 10 tokens/sec, Segment 2: 1 ghante baad 4 tokens/sec
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods.initializeRewards(
+      [
+        { startTs: new anchor.BN(now - 10), rate: new anchor.BN(10) },
+        { startTs: new anchor.BN(now + 3600), rate: new anchor.BN(4) },
+      ],
+      new anchor.BN(86400) // Har din halving
+    ).accounts({
+      config: config,
+      rewardVault: rewardVault,
+      rewardMint: rewardMint,
+      admin: userA.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc();
+
+    // Reward vault ko fund karo
+    await mintTo(provider.connection, userA.payer, rewardMint, rewardVault, userA.publicKey, 1_000_000);
+
+    const acc = await program.account.globalConfig.fetch(config);
+    assert.equal(acc.rewardMint.toBase58(), rewardMint.toBase58());
+    assert.equal(acc.emissionSegments.length, 2);
+    assert.equal(acc.halvingInterval.toNumber(), 86400);
+  });
+
+  it("NEGATIVE: Emission schedule cannot be committed twice", async () => {
+    // Naya mint taaki reward vault ka init pass ho aur check handler tak pahunche
+    const otherMint = await createMint(
+      provider.connection, userA.payer, userA.publicKey, null, 6
+    );
+    const [otherVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_vault"), otherMint.toBuffer()], program.programId
+    );
+    try {
+      await program.methods.initializeRewards(
+        [{ startTs: new anchor.BN(0), rate: new anchor.BN(1000) }],
+        new anchor.BN(0)
+      ).accounts({
+        config: config,
+        rewardVault: otherVault,
+        rewardMint: otherMint,
+        admin: userA.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "RewardsAlreadyInitialized");
+    }
+    const acc = await program.account.globalConfig.fetch(config);
+    assert.equal(acc.rewardMint.toBase58(), rewardMint.toBase58());
+  });
+
+  it("POSITIVE: Staker accrues and claims scheduled rewards", async () => {
+    await program.methods.deposit(new anchor.BN(100)).accounts({
+      staker: userA.publicKey,
+      vault: vault,
+      stakeInfo: userAStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userATokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(userA.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    // Thoda time guzarne do taaki reward bane
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods.claimRewards().accounts({
+      staker: userA.publicKey,
+      stakeInfo: userAStakeInfo,
+      config: config,
+      rewardMint: rewardMint,
+      rewardVault: rewardVault,
+      recipient: userARewardAccount,
+      stakerBlocklist: blocklistPda(userA.publicKey),
+      recipientBlocklist: blocklistPda(userA.publicKey),
+      boostAccount: null,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    }).rpc();
+
+    // Akela staker hai, to emission ka pura hissa User A ko milna chahiye
+    const rewardAcc = await getAccount(provider.connection, userARewardAccount);
+    assert.isAbove(Number(rewardAcc.amount), 0);
+
+    const info = await program.account.userStakeInfo.fetch(userAStakeInfo);
+    assert.equal(info.pendingRewards.toNumber(), 0);
+  });
+
+  // =========================================================================
+  // 6. AUTO-COMPOUNDING
+  // =========================================================================
+
+  it("POSITIVE: User A opts in to auto compound", async () => {
+    await program.methods.setAutoCompound(true).accounts({
+      staker: userA.publicKey,
+      stakeInfo: userAStakeInfo,
+    }).rpc();
+
+    const info = await program.account.userStakeInfo.fetch(userAStakeInfo);
+    assert.isTrue(info.autoCompound);
+  });
+
+  it("NEGATIVE: Cannot compound when reward mint differs from staked mint", async () => {
+    try {
+      await program.methods.compound().accounts({
+        staker: userA.publicKey,
+        stakeInfo: userAStakeInfo,
+        config: config,
+        vault: vault,
+        rewardVault: rewardVault,
+        mint: mint,
+        boostAccount: null,
+        stakerBlocklist: blocklistPda(userA.publicKey),
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Reward mint does not match the staked mint");
+    }
+  });
+
+  it("NEGATIVE: Compound bounty above the cap is rejected", async () => {
+    try {
+      await program.methods.updateCompoundBounty(new anchor.BN(5000)).accounts({
+        config: config,
+        admin: userA.publicKey
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Compound bounty exceeds the allowed maximum");
+    }
+  });
+
+  // =========================================================================
+  // 7. STAKE ON BEHALF OF ANOTHER WALLET
+  // =========================================================================
+
+  it("POSITIVE: User A stakes 50 tokens on behalf of User B", async () => {
+    const payerBefore = (await getAccount(provider.connection, userATokenAccount)).amount;
+    const beforeB = await program.account.userStakeInfo.fetch(userBStakeInfo);
+
+    await program.methods.depositFor(userB.publicKey, new anchor.BN(50)).accounts({
+      payer: userA.publicKey,
+      vault: vault,
+      stakeInfo: userBStakeInfo,
+      mint: mint,
+      payerTokenAccount: userATokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      payerBlocklist: blocklistPda(userA.publicKey),
+      beneficiaryBlocklist: blocklistPda(userB.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    // Position User B ki hai, paise User A ke wallet se gaye
+    const afterB = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(afterB.amount.toNumber(), beforeB.amount.toNumber() + 50);
+
+    const payerAfter = (await getAccount(provider.connection, userATokenAccount)).amount;
+    assert.equal(Number(payerBefore) - Number(payerAfter), 50);
+  });
+
+  // =========================================================================
+  // 8. WITHDRAW / CLAIM TO A DIFFERENT RECIPIENT
+  // =========================================================================
+
+  it("POSITIVE: User B withdraws to a cold wallet token account", async () => {
+    const coldWallet = anchor.web3.Keypair.generate();
+    const coldAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection, userA.payer, mint, coldWallet.publicKey
+    );
+    const staked = (await program.account.userStakeInfo.fetch(userBStakeInfo)).amount.toNumber();
+
+    // Fee 0% hai (section 4 me set hua), to pura amount cold wallet me jana chahiye
+    await program.methods.withdraw(new anchor.BN(staked)).accounts({
+      staker: userB.publicKey,
+      vault: vault,
+      stakeInfo: userBStakeInfo,
+      mint: mint,
+      recipient: coldAta.address,
+      feeVault: feeVault,
+      config: config,
+      boostAccount: null,
+      referralAccount: null,
+      insuranceVault: null,
+      stakerBlocklist: blocklistPda(userB.publicKey),
+      recipientBlocklist: blocklistPda(coldWallet.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).signers([userB]).rpc();
+
+    const coldAcc = await getAccount(provider.connection, coldAta.address);
+    assert.equal(Number(coldAcc.amount), staked);
+  });
+
+  it("NEGATIVE: Recipient with a different mint is rejected", async () => {
+    try {
+      await program.methods.claimRewards().accounts({
+        staker: userA.publicKey,
+        stakeInfo: userAStakeInfo,
+        config: config,
+        rewardMint: rewardMint,
+        rewardVault: rewardVault,
+        recipient: userATokenAccount, // Staked mint ka account, reward mint ka nahi
+        stakerBlocklist: blocklistPda(userA.publicKey),
+        recipientBlocklist: blocklistPda(userA.publicKey),
+        boostAccount: null,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "ConstraintTokenMint");
+    }
+  });
+
+  // =========================================================================
+  // 9. TRANSFERABLE STAKE POSITIONS
+  // =========================================================================
+
+  const newOwner = anchor.web3.Keypair.generate();
+  let newOwnerStakeInfo: anchor.web3.PublicKey;
+
+  it("NEGATIVE: Transfer fails while admin has paused transfers", async () => {
+    [newOwnerStakeInfo] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), newOwner.publicKey.toBuffer()], program.programId
+    );
+    await program.methods.setPositionTransfersPaused(true).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    try {
+      await program.methods.transferPosition(newOwner.publicKey).accounts({
+        owner: userA.publicKey,
+        fromStakeInfo: userAStakeInfo,
+        toStakeInfo: newOwnerStakeInfo,
+        ownerBlocklist: blocklistPda(userA.publicKey),
+        newOwnerBlocklist: blocklistPda(newOwner.publicKey),
+        config: config,
+        systemProgram: anchor.web3.SystemProgram.programId
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Position transfers are disabled");
+    }
+
+    await program.methods.setPositionTransfersPaused(false).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  const transferA = () =>
+    program.methods.transferPosition(newOwner.publicKey).accounts({
+      owner: userA.publicKey,
+      fromStakeInfo: userAStakeInfo,
+      toStakeInfo: newOwnerStakeInfo,
+      ownerBlocklist: blocklistPda(userA.publicKey),
+      newOwnerBlocklist: blocklistPda(newOwner.publicKey),
+      config: config,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+  it("NEGATIVE: Position cannot be transferred to a blocked wallet", async () => {
+    await blockWallet(newOwner.publicKey);
+    try {
+      await transferA();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "WalletBlocked");
+    }
+    await unblockWallet(newOwner.publicKey);
+  });
+
+  it("NEGATIVE: Blocked owner cannot transfer the position away", async () => {
+    await blockWallet(userA.publicKey);
+    try {
+      await transferA();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "WalletBlocked");
+    }
+    await unblockWallet(userA.publicKey);
+
+    // Ledger jaisa tha waisa hi hai
+    const info = await program.account.userStakeInfo.fetch(userAStakeInfo);
+    assert.isAbove(info.amount.toNumber(), 0);
+  });
+
+  it("POSITIVE: User A gifts the whole position to a new wallet", async () => {
+    const before = await program.account.userStakeInfo.fetch(userAStakeInfo);
+
+    await program.methods.transferPosition(newOwner.publicKey).accounts({
+      owner: userA.publicKey,
+      fromStakeInfo: userAStakeInfo,
+      toStakeInfo: newOwnerStakeInfo,
+      ownerBlocklist: blocklistPda(userA.publicKey),
+      newOwnerBlocklist: blocklistPda(newOwner.publicKey),
+      config: config,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    const moved = await program.account.userStakeInfo.fetch(newOwnerStakeInfo);
+    assert.equal(moved.amount.toNumber(), before.amount.toNumber());
+    assert.equal(moved.depositTs.toNumber(), before.depositTs.toNumber());
+    assert.isAtLeast(moved.pendingRewards.toNumber(), before.pendingRewards.toNumber());
+
+    // Purana ledger close ho gaya
+    const closed = await provider.connection.getAccountInfo(userAStakeInfo);
+    assert.isNull(closed);
+  });
+
+  // =========================================================================
+  // 10. NFT-REPRESENTED STAKE POSITIONS
+  // =========================================================================
+
+  let positionMint: anchor.web3.PublicKey;
+  let userBNftAccount: anchor.web3.PublicKey;
+
+  // Mint PDA = b"position_mint" + ledger + pool ka positions_tokenized nonce (u64 LE)
+  const nextPositionMint = async (stakeInfo: anchor.web3.PublicKey) => {
+    const acc = await program.account.globalConfig.fetch(config);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("position_mint"), stakeInfo.toBuffer(), acc.positionsTokenized.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
+  it("POSITIVE: User B stakes and tokenizes the position as an NFT", async () => {
+    await program.methods.deposit(new anchor.BN(100)).accounts({
+      staker: userB.publicKey,
+      vault: vault,
+      stakeInfo: userBStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userBTokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(userB.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).signers([userB]).rpc();
+
+    positionMint = await nextPositionMint(userBStakeInfo);
+    userBNftAccount = getAssociatedTokenAddressSync(positionMint, userB.publicKey);
+
+    await program.methods.tokenizePosition().accounts({
+      owner: userB.publicKey,
+      stakeInfo: userBStakeInfo,
+      config: config,
+      ownerBlocklist: blocklistPda(userB.publicKey),
+      positionMint: positionMint,
+      ownerNftAccount: userBNftAccount,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).signers([userB]).rpc();
+
+    const nftAcc = await getAccount(provider.connection, userBNftAccount);
+    assert.equal(Number(nftAcc.amount), 1);
+
+    const info = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(info.positionMint.toBase58(), positionMint.toBase58());
+  });
+
+  it("NEGATIVE: A tokenized position cannot be slashed to zero", async () => {
+    await program.methods.setSlashingAuthority(userA.publicKey).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    try {
+      await program.methods.slash(new anchor.BN(10000), 1).accounts({
+        slashingAuthority: userA.publicKey,
+        config: config,
+        stakeInfo: userBStakeInfo,
+        vault: vault,
+        mint: mint,
+        destination: null,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Position is represented by an NFT");
+    }
+    // NFT abhi bhi position redeem kar sakta hai
+    const info = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(info.amount.toNumber(), 100);
+    assert.equal(info.positionMint.toBase58(), positionMint.toBase58());
+  });
+
+  it("NEGATIVE: quote_withdraw fails for a tokenized position, like withdraw", async () => {
+    try {
+      await program.methods.quoteWithdraw(new anchor.BN(100)).accounts({
+        config: config,
+        stakeInfo: userBStakeInfo,
+      }).view();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "PositionTokenized");
+    }
+  });
+
+  it("NEGATIVE: Wallet-keyed withdraw is blocked for a tokenized position", async () => {
+    try {
+      await program.methods.withdraw(new anchor.BN(100)).accounts({
+        staker: userB.publicKey,
+        vault: vault,
+        stakeInfo: userBStakeInfo,
+        mint: mint,
+        recipient: userBTokenAccount,
+        feeVault: feeVault,
+        config: config,
+        boostAccount: null,
+        referralAccount: null,
+        insuranceVault: null,
+        stakerBlocklist: blocklistPda(userB.publicKey),
+        recipientBlocklist: blocklistPda(userB.publicKey),
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).signers([userB]).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Position is represented by an NFT");
+    }
+  });
+
+  it("NEGATIVE: NFT withdraw cannot pay rewards to a blocked wallet's account", async () => {
+    const blockedWallet = anchor.web3.Keypair.generate().publicKey;
+    const blockedRewardAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection, userA.payer, rewardMint, blockedWallet
+    );
+    const withdrawNft = (rewardRecipientBlocklist: anchor.web3.PublicKey | null) =>
+      program.methods.withdrawNftPosition().accounts({
+        holder: userB.publicKey,
+        stakeInfo: userBStakeInfo,
+        positionMint: positionMint,
+        holderNftAccount: userBNftAccount,
+        vault: vault,
+        mint: mint,
+        recipient: userBTokenAccount,
+        feeVault: feeVault,
+        config: config,
+        rewardMint: rewardMint,
+        rewardVault: rewardVault,
+        rewardRecipient: blockedRewardAta.address,
+        rewardRecipientBlocklist,
+        referralAccount: null,
+        insuranceVault: null,
+        holderBlocklist: blocklistPda(userB.publicKey),
+        recipientBlocklist: blocklistPda(userB.publicKey),
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).signers([userB]).rpc();
+
+    // Blocklist PDA dena hi padega (ya galat PDA)
+    for (const entry of [null, blocklistPda(userB.publicKey)]) {
+      try {
+        await withdrawNft(entry);
+        assert.fail("Should fail");
+      } catch (e) {
+        assert.include(e.message, "BlocklistAccountMismatch");
+      }
+    }
+
+    await blockWallet(blockedWallet);
+    try {
+      await withdrawNft(blocklistPda(blockedWallet));
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "WalletBlocked");
+    }
+    await unblockWallet(blockedWallet);
+  });
+
+  it("POSITIVE: NFT holder burns the NFT and withdraws the position", async () => {
+    const rewardAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection, userA.payer, rewardMint, userB.publicKey
+    );
+    const before = (await getAccount(provider.connection, userBTokenAccount)).amount;
+
+    await program.methods.withdrawNftPosition().accounts({
+      holder: userB.publicKey,
+      stakeInfo: userBStakeInfo,
+      positionMint: positionMint,
+      holderNftAccount: userBNftAccount,
+      vault: vault,
+      mint: mint,
+      recipient: userBTokenAccount,
+      feeVault: feeVault,
+      config: config,
+      rewardMint: rewardMint,
+      rewardVault: rewardVault,
+      rewardRecipient: rewardAta.address,
+      rewardRecipientBlocklist: blocklistPda(userB.publicKey),
+      referralAccount: null,
+      insuranceVault: null,
+      holderBlocklist: blocklistPda(userB.publicKey),
+      recipientBlocklist: blocklistPda(userB.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).signers([userB]).rpc();
+
+    // Fee 0% hai, pura 100 wapis
+    const after = (await getAccount(provider.connection, userBTokenAccount)).amount;
+    assert.equal(Number(after) - Number(before), 100);
+
+    const nftAcc = await getAccount(provider.connection, userBNftAccount);
+    assert.equal(Number(nftAcc.amount), 0);
+
+    const info = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(info.amount.toNumber(), 0);
+    assert.equal(info.positionMint.toBase58(), anchor.web3.PublicKey.default.toBase58());
+  });
+
+  it("POSITIVE: A redeemed position can be tokenized again with a fresh NFT", async () => {
+    await program.methods.deposit(new anchor.BN(100)).accounts({
+      staker: userB.publicKey,
+      vault: vault,
+      stakeInfo: userBStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userBTokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(userB.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).signers([userB]).rpc();
+
+    const secondMint = await nextPositionMint(userBStakeInfo);
+    assert.notEqual(secondMint.toBase58(), positionMint.toBase58());
+    const secondNftAccount = getAssociatedTokenAddressSync(secondMint, userB.publicKey);
+
+    await program.methods.tokenizePosition().accounts({
+      owner: userB.publicKey,
+      stakeInfo: userBStakeInfo,
+      config: config,
+      ownerBlocklist: blocklistPda(userB.publicKey),
+      positionMint: secondMint,
+      ownerNftAccount: secondNftAccount,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).signers([userB]).rpc();
+    assert.equal(Number((await getAccount(provider.connection, secondNftAccount)).amount), 1);
+
+    // Agle sections ke liye position wapis redeem kar do
+    await program.methods.withdrawNftPosition().accounts({
+      holder: userB.publicKey,
+      stakeInfo: userBStakeInfo,
+      positionMint: secondMint,
+      holderNftAccount: secondNftAccount,
+      vault: vault,
+      mint: mint,
+      recipient: userBTokenAccount,
+      feeVault: feeVault,
+      config: config,
+      rewardMint: rewardMint,
+      rewardVault: rewardVault,
+      rewardRecipient: getAssociatedTokenAddressSync(rewardMint, userB.publicKey),
+      rewardRecipientBlocklist: blocklistPda(userB.publicKey),
+      referralAccount: null,
+      insuranceVault: null,
+      holderBlocklist: blocklistPda(userB.publicKey),
+      recipientBlocklist: blocklistPda(userB.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).signers([userB]).rpc();
+
+    const info = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(info.amount.toNumber(), 0);
+  });
+
+  // =========================================================================
+  // 11. REWARD BOOSTS FROM PARTNER HOLDINGS
+  // =========================================================================
+
+  let partnerMint: anchor.web3.PublicKey;
+  let userBPartnerAccount: anchor.web3.PublicKey;
+  const [userBBoostEscrow] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("boost_escrow"),
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), userB.publicKey.toBuffer()], program.programId
+      )[0].toBuffer(),
+    ],
+    program.programId
+  );
+  const registerBoostB = (tier: number, boostMint: anchor.web3.PublicKey, boostMetadata: anchor.web3.PublicKey | null = null) =>
+    program.methods.registerBoost(tier).accounts({
+      staker: userB.publicKey,
+      stakeInfo: userBStakeInfo,
+      config: config,
+      boostMint: boostMint,
+      stakerBoostAccount: getAssociatedTokenAddressSync(boostMint, userB.publicKey),
+      boostEscrow: userBBoostEscrow,
+      boostMetadata: boostMetadata,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([userB]).rpc();
+  const unregisterBoostB = (boostMint: anchor.web3.PublicKey) =>
+    program.methods.unregisterBoost().accounts({
+      staker: userB.publicKey,
+      stakeInfo: userBStakeInfo,
+      config: config,
+      boostMint: boostMint,
+      stakerBoostAccount: getAssociatedTokenAddressSync(boostMint, userB.publicKey),
+      boostEscrow: userBBoostEscrow,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    }).signers([userB]).rpc();
+
+  it("POSITIVE: Admin configures a 1.5x boost for holding 10 partner tokens", async () => {
+    partnerMint = await createMint(
+      provider.connection, userA.payer, userA.publicKey, null, 0
+    );
+    const ata = await getOrCreateAssociatedTokenAccount(
+      provider.connection, userA.payer, partnerMint, userB.publicKey
+    );
+    userBPartnerAccount = ata.address;
+    await mintTo(provider.connection, userA.payer, partnerMint, userBPartnerAccount, userA.publicKey, 5);
+
+    await program.methods.setBoostTiers([
+      { mint: partnerMint, minBalance: new anchor.BN(10), multiplierBps: new anchor.BN(15000), collection: false },
+    ]).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    const acc = await program.account.globalConfig.fetch(config);
+    assert.equal(acc.boostTiers.length, 1);
+  });
+
+  it("NEGATIVE: Cannot register boost without enough partner tokens", async () => {
+    await program.methods.deposit(new anchor.BN(100)).accounts({
+      staker: userB.publicKey,
+      vault: vault,
+      stakeInfo: userBStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userBTokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(userB.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).signers([userB]).rpc();
+
+    try {
+      await registerBoostB(0, partnerMint);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Boost account does not meet the tier requirement");
+    }
+  });
+
+  it("POSITIVE: Boost multiplies reward weight once the holding is met", async () => {
+    await mintTo(provider.connection, userA.payer, partnerMint, userBPartnerAccount, userA.publicKey, 5);
+    const before = await program.account.globalConfig.fetch(config);
+
+    await registerBoostB(0, partnerMint);
+
+    const info = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(info.boostBps.toNumber(), 15000);
+    assert.equal(info.boostAccount.toBase58(), userBBoostEscrow.toBase58());
+
+    // Tokens escrow me lock hain, wallet me kuch nahi bacha (udhaar tokens se boost nahi le sakte)
+    assert.equal(Number((await getAccount(provider.connection, userBPartnerAccount)).amount), 0);
+    assert.equal(Number((await getAccount(provider.connection, userBBoostEscrow)).amount), 10);
+
+    // 100 staked -> weight 150, yaani total weight 50 se badha
+    const after = await program.account.globalConfig.fetch(config);
+    assert.equal(after.totalWeight.toNumber() - before.totalWeight.toNumber(), 50);
+  });
+
+  it("NEGATIVE: Boosted staker must present the boost account", async () => {
+    try {
+      await program.methods.claimRewards().accounts({
+        staker: userB.publicKey,
+        stakeInfo: userBStakeInfo,
+        config: config,
+        rewardMint: rewardMint,
+        rewardVault: rewardVault,
+        recipient: getAssociatedTokenAddressSync(rewardMint, userB.publicKey),
+        stakerBlocklist: blocklistPda(userB.publicKey),
+        recipientBlocklist: blocklistPda(userB.publicKey),
+        boostAccount: null,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      }).signers([userB]).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "The registered boost account must be provided");
+    }
+  });
+
+  it("POSITIVE: unregister_boost removes the boost and returns the escrowed tokens", async () => {
+    const before = await program.account.globalConfig.fetch(config);
+    await unregisterBoostB(partnerMint);
+
+    const info = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(info.boostBps.toNumber(), 0);
+    assert.equal(info.boostAccount.toBase58(), anchor.web3.PublicKey.default.toBase58());
+    assert.equal(Number((await getAccount(provider.connection, userBPartnerAccount)).amount), 10);
+    assert.isNull(await provider.connection.getAccountInfo(userBBoostEscrow)); // Escrow band
+
+    const after = await program.account.globalConfig.fetch(config);
+    assert.equal(before.totalWeight.toNumber() - after.totalWeight.toNumber(), 50);
+  });
+
+  // Anchor.toml se preload: dono NFT mints userA ke hain, metadata collection GvK88... me hai
+  const collectionMint = new anchor.web3.PublicKey("GvK88E8SKUHAZGKziGd6FQzabKCAz7f15Wtkk8bS2Ddo");
+  const verifiedNft = new anchor.web3.PublicKey("2jV5NuiwB4Mi1sPqUH6HVp3LbvTUtG6z3iUTUp1cst5s");
+  const unverifiedNft = new anchor.web3.PublicKey("7hoLD6qUzk88uYffMi2w3oKX6KsgbefHYATSSoieWYt9");
+  const metadataProgram = new anchor.web3.PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+  const metadataPda = (nft: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), metadataProgram.toBuffer(), nft.toBuffer()], metadataProgram
+    )[0];
+
+  it("POSITIVE: Any verified NFT of a collection tier's collection earns the boost", async () => {
+    await program.methods.setBoostTiers([
+      { mint: partnerMint, minBalance: new anchor.BN(10), multiplierBps: new anchor.BN(15000), collection: false },
+      { mint: collectionMint, minBalance: new anchor.BN(1), multiplierBps: new anchor.BN(20000), collection: true },
+    ]).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    for (const nft of [verifiedNft, unverifiedNft]) {
+      const ata = await getOrCreateAssociatedTokenAccount(provider.connection, userA.payer, nft, userB.publicKey);
+      await mintTo(provider.connection, userA.payer, nft, ata.address, userA.publicKey, 1);
+    }
+
+    await registerBoostB(1, verifiedNft, metadataPda(verifiedNft));
+
+    const info = await program.account.userStakeInfo.fetch(userBStakeInfo);
+    assert.equal(info.boostBps.toNumber(), 20000);
+    assert.equal(info.boostCollection.toBase58(), collectionMint.toBase58());
+    assert.equal(Number((await getAccount(provider.connection, userBBoostEscrow)).amount), 1);
+
+    await unregisterBoostB(verifiedNft);
+    assert.equal(
+      Number((await getAccount(provider.connection, getAssociatedTokenAddressSync(verifiedNft, userB.publicKey))).amount), 1
+    );
+  });
+
+  it("NEGATIVE: Collection tier rejects unverified NFTs and NFTs without metadata", async () => {
+    const attempts: [anchor.web3.PublicKey, anchor.web3.PublicKey | null][] = [
+      [unverifiedNft, metadataPda(unverifiedNft)], // Collection set hai par verified nahi
+      [unverifiedNft, metadataPda(verifiedNft)],   // Kisi aur NFT ka metadata
+      [verifiedNft, null],                         // Metadata diya hi nahi
+      [partnerMint, null],                         // Collection wale tier me plain partner token
+    ];
+    for (const [nft, metadata] of attempts) {
+      try {
+        await registerBoostB(1, nft, metadata);
+        assert.fail("Should fail");
+      } catch (e) {
+        assert.include(e.message, "Boost account does not meet the tier requirement");
+      }
+    }
+  });
+
+  // =========================================================================
+  // 12. REFERRAL REWARDS
+  // =========================================================================
+
+  const userC = anchor.web3.Keypair.generate();
+  let userCTokenAccount: anchor.web3.PublicKey;
+  let userCStakeInfo: anchor.web3.PublicKey;
+  let referralAccountA: anchor.web3.PublicKey;
+
+  it("POSITIVE: User A registers as referrer and User C stakes with the referral", async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(userC.publicKey, 1000000000)
+    );
+    const ataC = await getOrCreateAssociatedTokenAccount(
+      provider.connection, userA.payer, mint, userC.publicKey
+    );
+    userCTokenAccount = ataC.address;
+    await mintTo(provider.connection, userA.payer, mint, userCTokenAccount, userA.publicKey, 1000);
+
+    [userCStakeInfo] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), userC.publicKey.toBuffer()], program.programId
+    );
+    [referralAccountA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("referral"), userA.publicKey.toBuffer()], program.programId
+    );
+
+    await program.methods.registerReferrer().accounts({
+      referrer: userA.publicKey,
+      referralAccount: referralAccountA,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    await program.methods.deposit(new anchor.BN(100)).accounts({
+      staker: userC.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userCTokenAccount,
+      config: config,
+      referralAccount: referralAccountA,
+      allowlistEntry: null,
+      boostAccount: null,
+      stakerBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).signers([userC]).rpc();
+
+    const info = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(info.referrer.toBase58(), userA.publicKey.toBase58());
+  });
+
+  it("POSITIVE: Half of the withdraw fee goes to the referrer", async () => {
+    // Fee 10%, referral share 50% -> fee 10, referrer 5, fee vault 5
+    await program.methods.updateFee(new anchor.BN(1000)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await program.methods.updateReferralShare(new anchor.BN(5000)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    const feeBefore = (await getAccount(provider.connection, feeVault)).amount;
+
+    await program.methods.withdraw(new anchor.BN(100)).accounts({
+      staker: userC.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      recipient: userCTokenAccount,
+      feeVault: feeVault,
+      config: config,
+      referralAccount: referralAccountA,
+      boostAccount: null,
+      insuranceVault: null,
+      stakerBlocklist: blocklistPda(userC.publicKey),
+      recipientBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).signers([userC]).rpc();
+
+    const feeAfter = (await getAccount(provider.connection, feeVault)).amount;
+    assert.equal(Number(feeAfter) - Number(feeBefore), 5);
+
+    const referral = await program.account.referralAccount.fetch(referralAccountA);
+    assert.equal(referral.claimable.toNumber(), 5);
+
+    // Baaki tests ke liye fee wapis 0
+    await program.methods.updateFee(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  it("POSITIVE: Referrer claims the referral balance", async () => {
+    const before = (await getAccount(provider.connection, userATokenAccount)).amount;
+
+    await program.methods.claimReferralRewards().accounts({
+      referrer: userA.publicKey,
+      referralAccount: referralAccountA,
+      vault: vault,
+      mint: mint,
+      recipient: userATokenAccount,
+      referrerBlocklist: blocklistPda(userA.publicKey),
+      recipientBlocklist: blocklistPda(userA.publicKey),
+      config: config,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).rpc();
+
+    const after = (await getAccount(provider.connection, userATokenAccount)).amount;
+    assert.equal(Number(after) - Number(before), 5);
+
+    const referral = await program.account.referralAccount.fetch(referralAccountA);
+    assert.equal(referral.claimable.toNumber(), 0);
+    assert.equal(referral.totalEarned.toNumber(), 5);
+  });
+
+  // =========================================================================
+  // 13. DEPOSIT CAPS
+  // =========================================================================
+
+  const depositC = (amount: number) =>
+    program.methods.deposit(new anchor.BN(amount)).accounts({
+      staker: userC.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userCTokenAccount,
+      config: config,
+      referralAccount: null,
+      allowlistEntry: null,
+      boostAccount: null,
+      stakerBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).signers([userC]).rpc();
+
+  it("NEGATIVE: Deposit above the per-user cap is rejected", async () => {
+    await program.methods.updateDepositCaps(new anchor.BN(0), new anchor.BN(50)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    try {
+      await depositC(60);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Deposit would exceed the per-user stake cap");
+    }
+  });
+
+  it("NEGATIVE: Deposit above the pool cap is rejected", async () => {
+    const acc = await program.account.globalConfig.fetch(config);
+    await program.methods.updateDepositCaps(acc.totalStaked.addn(10), new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    try {
+      await depositC(20);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Deposit would exceed the pool's total stake cap");
+    }
+
+    // Limit ke andar wala deposit chalega
+    await depositC(10);
+    const info = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(info.amount.toNumber(), 10);
+
+    // Caps hatao
+    await program.methods.updateDepositCaps(new anchor.BN(0), new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  // =========================================================================
+  // 14. MINIMUM STAKE & PARTIAL WITHDRAWS
+  // =========================================================================
+
+  const withdrawC = (
+    amount: number,
+    insuranceVault: anchor.web3.PublicKey | null = null,
+    feeRecipients: anchor.web3.PublicKey[] = []
+  ) =>
+    program.methods.withdraw(new anchor.BN(amount)).accounts({
+      staker: userC.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      recipient: userCTokenAccount,
+      feeVault: feeVault,
+      config: config,
+      referralAccount: referralAccountA,
+      boostAccount: null,
+      insuranceVault: insuranceVault,
+      stakerBlocklist: blocklistPda(userC.publicKey),
+      recipientBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).remainingAccounts(
+      feeRecipients.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+    ).signers([userC]).rpc();
+
+  it("NEGATIVE: Deposit that leaves the position below the minimum is rejected", async () => {
+    await program.methods.updateMinPosition(new anchor.BN(50)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    // User C ke paas 10 staked hai, +20 = 30 < 50
+    try {
+      await depositC(20);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Position would be below the minimum stake");
+    }
+  });
+
+  it("POSITIVE: Partial withdraw that keeps the minimum succeeds", async () => {
+    await depositC(90); // 10 + 90 = 100
+    await withdrawC(40); // 60 bache, min 50 se upar
+
+    const info = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(info.amount.toNumber(), 60);
+  });
+
+  it("NEGATIVE: Partial withdraw that leaves dust must withdraw everything", async () => {
+    try {
+      await withdrawC(20); // 40 bachte, min 50 se kam
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "withdraw everything instead");
+    }
+
+    await withdrawC(60); // Pura nikalna chalega
+    const info = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(info.amount.toNumber(), 0);
+
+    await program.methods.updateMinPosition(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  // =========================================================================
+  // 15. ALLOWLIST-GATED POOL
+  // =========================================================================
+
+  let userCAllowlistEntry: anchor.web3.PublicKey;
+
+  it("NEGATIVE: Non-allowlisted wallet cannot deposit into a gated pool", async () => {
+    [userCAllowlistEntry] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("allowlist"), userC.publicKey.toBuffer()], program.programId
+    );
+    await program.methods.setAllowlistEnabled(true).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    try {
+      await depositC(100);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Wallet is not on the pool allowlist");
+    }
+  });
+
+  it("POSITIVE: Admin-added allowlist entry lets the wallet deposit", async () => {
+    await program.methods.addAllowlistEntry(userC.publicKey).accounts({
+      config: config,
+      allowlistEntry: userCAllowlistEntry,
+      admin: userA.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    await program.methods.deposit(new anchor.BN(100)).accounts({
+      staker: userC.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userCTokenAccount,
+      config: config,
+      referralAccount: null,
+      allowlistEntry: userCAllowlistEntry,
+      boostAccount: null,
+      stakerBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).signers([userC]).rpc();
+
+    const info = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(info.amount.toNumber(), 100);
+  });
+
+  it("POSITIVE: Admin removes the entry and opens the pool again", async () => {
+    await program.methods.removeAllowlistEntry().accounts({
+      config: config,
+      allowlistEntry: userCAllowlistEntry,
+      admin: userA.publicKey,
+    }).rpc();
+    assert.isNull(await provider.connection.getAccountInfo(userCAllowlistEntry));
+
+    await program.methods.setAllowlistEnabled(false).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  // =========================================================================
+  // 16. POOL STATISTICS
+  // =========================================================================
+
+  it("POSITIVE: Full withdraw and re-deposit update pool statistics", async () => {
+    const staked = (await program.account.userStakeInfo.fetch(userCStakeInfo)).amount.toNumber();
+    const before = await program.account.globalConfig.fetch(config);
+
+    await withdrawC(staked);
+    const afterWithdraw = await program.account.globalConfig.fetch(config);
+    assert.equal(afterWithdraw.activeStakers.toNumber(), before.activeStakers.toNumber() - 1);
+    assert.equal(afterWithdraw.totalStaked.toNumber(), before.totalStaked.toNumber() - staked);
+    assert.equal(
+      afterWithdraw.cumulativeWithdrawals.sub(before.cumulativeWithdrawals).toNumber(), staked
+    );
+    // Fee abhi 0% hai
+    assert.isTrue(afterWithdraw.cumulativeFees.eq(before.cumulativeFees));
+
+    await depositC(staked);
+    const afterDeposit = await program.account.globalConfig.fetch(config);
+    assert.equal(afterDeposit.activeStakers.toNumber(), before.activeStakers.toNumber());
+    assert.equal(afterDeposit.totalStaked.toNumber(), before.totalStaked.toNumber());
+    assert.equal(
+      afterDeposit.cumulativeDeposits.sub(before.cumulativeDeposits).toNumber(), staked
+    );
+  });
+
+  // =========================================================================
+  // 17. SOLVENCY CHECK & SURPLUS SWEEP
+  // =========================================================================
+
+  const checkSolvency = async () => {
+    const sim = await program.methods.verifySolvency().accounts({
+      config: config,
+      vault: vault,
+      mint: mint,
+      rewardVault: rewardVault,
+    }).simulate();
+    return sim.events.find((e) => e.name === "SolvencyChecked").data;
+  };
+
+  it("POSITIVE: verify_solvency reports surplus from stray tokens in the vault", async () => {
+    const before = await checkSolvency();
+    assert.isTrue(before.vaultSurplus.gten(0));
+    assert.isTrue(before.rewardSurplus.gten(0));
+
+    // Koi galti se vault me 10 tokens bhej deta hai
+    await mintTo(provider.connection, userA.payer, mint, vault, userA.publicKey, 10);
+
+    const after = await checkSolvency();
+    assert.equal(after.vaultSurplus.sub(before.vaultSurplus).toNumber(), 10);
+    assert.isTrue(after.vaultLiabilities.eq(before.vaultLiabilities));
+  });
+
+  it("NEGATIVE: Hacker cannot sweep surplus", async () => {
+    try {
+      await program.methods.sweepSurplus(new anchor.BN(1)).accounts({
+        config: config,
+        vault: vault,
+        mint: mint,
+        destination: feeVault,
+        admin: hacker.publicKey,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).signers([hacker]).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "ConstraintHasOne");
+    }
+  });
+
+  it("POSITIVE: Admin sweeps exactly the surplus, never principal", async () => {
+    const surplus = (await checkSolvency()).vaultSurplus.toNumber();
+
+    try {
+      await program.methods.sweepSurplus(new anchor.BN(surplus + 1)).accounts({
+        config: config,
+        vault: vault,
+        mint: mint,
+        destination: feeVault,
+        admin: userA.publicKey,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Sweep amount exceeds the vault surplus");
+    }
+
+    await program.methods.sweepSurplus(new anchor.BN(surplus)).accounts({
+      config: config,
+      vault: vault,
+      mint: mint,
+      destination: feeVault,
+      admin: userA.publicKey,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).rpc();
+
+    const after = await checkSolvency();
+    assert.equal(after.vaultSurplus.toNumber(), 0);
+  });
+
+  // =========================================================================
+  // 18. READ-ONLY QUOTES
+  // =========================================================================
+
+  it("POSITIVE: quote_withdraw matches the real withdraw", async () => {
+    await program.methods.updateFee(new anchor.BN(300)).accounts({ // 3%
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    const quote = await program.methods.quoteWithdraw(new anchor.BN(50)).accounts({
+      config: config,
+      stakeInfo: userCStakeInfo,
+    }).view();
+    assert.equal(quote.fee.toNumber(), 1); // 50 * 3% = 1.5 -> 1 (round down)
+    assert.equal(quote.userAmount.toNumber(), 49);
+
+    const before = (await getAccount(provider.connection, userCTokenAccount)).amount;
+    await withdrawC(50);
+    const after = (await getAccount(provider.connection, userCTokenAccount)).amount;
+    assert.equal(Number(after - before), quote.userAmount.toNumber());
+
+    await program.methods.updateFee(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  it("NEGATIVE: quote_withdraw rejects more than the position", async () => {
+    try {
+      await program.methods.quoteWithdraw(new anchor.BN(1_000_000)).accounts({
+        config: config,
+        stakeInfo: userCStakeInfo,
+      }).view();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "InsufficientStake");
+    }
+  });
+
+  it("POSITIVE: quote_rewards returns pending rewards without changing state", async () => {
+    const infoBefore = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    const pending = await program.methods.quoteRewards(userC.publicKey).accounts({
+      config: config,
+      stakeInfo: userCStakeInfo,
+    }).view();
+    assert.isTrue(pending.gte(infoBefore.pendingRewards));
+
+    const infoAfter = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.isTrue(infoAfter.rewardDebt.eq(infoBefore.rewardDebt));
+  });
+
+  // =========================================================================
+  // 19. SLASHING
+  // =========================================================================
+
+  const slashC = (bps: number, reason: number, signer: anchor.web3.Keypair | null, destination: anchor.web3.PublicKey | null) => {
+    const builder = program.methods.slash(new anchor.BN(bps), reason).accounts({
+      slashingAuthority: signer ? signer.publicKey : userA.publicKey,
+      config: config,
+      stakeInfo: userCStakeInfo,
+      vault: vault,
+      mint: mint,
+      destination: destination,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    });
+    return signer ? builder.signers([signer]).rpc() : builder.rpc();
+  };
+
+  it("NEGATIVE: Only the slashing authority can slash", async () => {
+    await program.methods.setSlashingAuthority(userA.publicKey).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    try {
+      await slashC(1000, 1, hacker, null);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "You are not authorized to perform this action.");
+    }
+  });
+
+  it("POSITIVE: Slash burns 10% of a position", async () => {
+    const before = (await program.account.userStakeInfo.fetch(userCStakeInfo)).amount.toNumber();
+    const supplyBefore = (await getMint(provider.connection, mint)).supply;
+
+    await slashC(1000, 7, null, null); // reason 7, burn
+
+    const after = (await program.account.userStakeInfo.fetch(userCStakeInfo)).amount.toNumber();
+    const slashed = Math.floor(before / 10);
+    assert.equal(after, before - slashed);
+    const supplyAfter = (await getMint(provider.connection, mint)).supply;
+    assert.equal(Number(supplyBefore - supplyAfter), slashed);
+  });
+
+  let insuranceVault: anchor.web3.PublicKey;
+
+  it("NEGATIVE: Slash cannot send tokens to a third-party account", async () => {
+    try {
+      await slashC(1000, 3, null, feeVault);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "ConstraintSeeds");
+    }
+  });
+
+  it("POSITIVE: Slash can send tokens to the insurance vault", async () => {
+    [insuranceVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance"), mint.toBuffer()], program.programId
+    );
+    await program.methods.initializeInsurance().accounts({
+      config: config,
+      insuranceVault: insuranceVault,
+      mint: mint,
+      admin: userA.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY
+    }).rpc();
+
+    const before = (await program.account.userStakeInfo.fetch(userCStakeInfo)).amount.toNumber();
+    await slashC(10000, 2, null, insuranceVault); // 100%
+
+    const info = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(info.amount.toNumber(), 0);
+    const dest = await getAccount(provider.connection, insuranceVault);
+    assert.equal(Number(dest.amount), before);
+  });
+
+  // =========================================================================
+  // 20. INSURANCE FUND
+  // =========================================================================
+
+  it("POSITIVE: Admin sets an insurance fee share", async () => {
+    // Insurance vault slashing section me ban chuka hai
+    await program.methods.updateInsuranceShare(new anchor.BN(5000)).accounts({ // fee ka 50%
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await program.methods.updateFee(new anchor.BN(1000)).accounts({ // 10%
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.insuranceFeeShareBps.toNumber(), 5000);
+  });
+
+  it("NEGATIVE: Withdraw without the insurance vault fails when a share is set", async () => {
+    await depositC(100);
+    try {
+      await withdrawC(100);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Insurance vault account is required");
+    }
+  });
+
+  it("POSITIVE: Part of the withdraw fee goes to the insurance vault", async () => {
+    const quote = await program.methods.quoteWithdraw(new anchor.BN(100)).accounts({
+      config: config,
+      stakeInfo: userCStakeInfo,
+    }).view();
+    assert.equal(quote.fee.toNumber(), 10);
+    assert.equal(quote.insuranceFee.toNumber(), 5);
+
+    const insuranceBefore = Number((await getAccount(provider.connection, insuranceVault)).amount);
+    await withdrawC(100, insuranceVault);
+
+    const insurance = await getAccount(provider.connection, insuranceVault);
+    assert.equal(Number(insurance.amount) - insuranceBefore, 5);
+  });
+
+  it("NEGATIVE: cover_loss cannot move insurance funds when there is no deficit", async () => {
+    try {
+      await program.methods.coverLoss(new anchor.BN(1)).accounts({
+        config: config,
+        insuranceVault: insuranceVault,
+        vault: vault,
+        mint: mint,
+        admin: userA.publicKey,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Amount exceeds the staking vault deficit");
+    }
+
+    await program.methods.updateFee(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  // =========================================================================
+  // 21. FEE SPLIT
+  // =========================================================================
+
+  let treasuryFeeAccount: anchor.web3.PublicKey;
+  let partnerFeeAccount: anchor.web3.PublicKey;
+
+  it("NEGATIVE: Fee split with duplicate recipients is rejected", async () => {
+    treasuryFeeAccount = await createAccount(provider.connection, userA.payer, mint, anchor.web3.Keypair.generate().publicKey);
+    partnerFeeAccount = await createAccount(provider.connection, userA.payer, mint, anchor.web3.Keypair.generate().publicKey);
+
+    try {
+      await program.methods.setFeeSplit([
+        { tokenAccount: treasuryFeeAccount, bps: 5000 },
+        { tokenAccount: treasuryFeeAccount, bps: 5000 },
+      ]).accounts({ config: config, admin: userA.publicKey }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Fee split needs 1-5 unique recipients");
+    }
+  });
+
+  it("POSITIVE: Admin fee is split across the configured recipients", async () => {
+    await program.methods.setFeeSplit([
+      { tokenAccount: treasuryFeeAccount, bps: 6000 },
+      { tokenAccount: partnerFeeAccount, bps: 4000 },
+    ]).accounts({ config: config, admin: userA.publicKey }).rpc();
+    await program.methods.updateInsuranceShare(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await program.methods.updateFee(new anchor.BN(1000)).accounts({ // 10%
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    await depositC(200);
+    const quote = await program.methods.quoteWithdraw(new anchor.BN(200)).accounts({
+      config: config,
+      stakeInfo: userCStakeInfo,
+    }).view();
+    const adminFee = quote.fee.toNumber() - quote.referralFee.toNumber() - quote.insuranceFee.toNumber();
+    const feeVaultBefore = Number((await getAccount(provider.connection, feeVault)).amount);
+
+    await withdrawC(200, null, [treasuryFeeAccount, partnerFeeAccount]);
+
+    const treasury = Number((await getAccount(provider.connection, treasuryFeeAccount)).amount);
+    const partner = Number((await getAccount(provider.connection, partnerFeeAccount)).amount);
+    assert.equal(treasury, Math.floor(adminFee * 6000 / 10000));
+    assert.equal(treasury + partner, adminFee);
+    // fee_vault ko kuch nahi mila
+    assert.equal(Number((await getAccount(provider.connection, feeVault)).amount), feeVaultBefore);
+  });
+
+  it("NEGATIVE: Withdraw with recipients in the wrong order fails", async () => {
+    await depositC(100);
+    try {
+      await withdrawC(100, null, [partnerFeeAccount, treasuryFeeAccount]);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Remaining account does not match the configured fee recipient");
+    }
+
+    // Wapas single fee_vault pe
+    await program.methods.setFeeSplit([]).accounts({ config: config, admin: userA.publicKey }).rpc();
+    await withdrawC(100);
+    await program.methods.updateFee(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
+  // =========================================================================
+  // 22. LOYALTY FEE DISCOUNTS
+  // =========================================================================
+
+  const quoteC = (amount: number) =>
+    program.methods.quoteWithdraw(new anchor.BN(amount)).accounts({
+      config: config,
+      stakeInfo: userCStakeInfo,
+    }).view();
+
+  it("NEGATIVE: Loyalty discount above 100% is rejected", async () => {
+    try {
+      await program.methods.setLoyaltyTiers([
+        { minDuration: new anchor.BN(0), minAmount: new anchor.BN(0), discountBps: new anchor.BN(10001) },
+      ]).accounts({ config: config, admin: userA.publicKey }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Loyalty tier discount must be 1-10000 bps");
+    }
+  });
+
+  it("POSITIVE: Large positions get the tier discount on the withdraw fee", async () => {
+    await program.methods.setLoyaltyTiers([
+      { minDuration: new anchor.BN(0), minAmount: new anchor.BN(150), discountBps: new anchor.BN(5000) }, // 150+ pe fee aadhi
+    ]).accounts({ config: config, admin: userA.publicKey }).rpc();
+    await program.methods.updateFee(new anchor.BN(1000)).accounts({ // 10%
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    await depositC(200);
+    const quote = await quoteC(200);
+    assert.equal(quote.feeDiscountBps.toNumber(), 5000);
+    assert.equal(quote.fee.toNumber(), 10); // 200 * 10% * 50%
+
+    const before = Number((await getAccount(provider.connection, userCTokenAccount)).amount);
+    await withdrawC(200);
+    const after = Number((await getAccount(provider.connection, userCTokenAccount)).amount);
+    assert.equal(after - before, 190);
+  });
+
+  it("POSITIVE: Positions below the tier threshold pay the full fee", async () => {
+    await depositC(100);
+    const quote = await quoteC(100);
+    assert.equal(quote.feeDiscountBps.toNumber(), 0);
+    assert.equal(quote.fee.toNumber(), 10);
+
+    await program.methods.setLoyaltyTiers([]).accounts({ config: config, admin: userA.publicKey }).rpc();
+    await program.methods.updateFee(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await withdrawC(100);
+  });
+
+  it("POSITIVE: deposit_for top-ups do not reset the beneficiary's loyalty clock", async () => {
+    await depositC(100);
+    const start = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    await new Promise((r) => setTimeout(r, 2000));
+
+    await program.methods.depositFor(userC.publicKey, new anchor.BN(50)).accounts({
+      payer: userA.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      payerTokenAccount: userATokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      payerBlocklist: blocklistPda(userA.publicKey),
+      beneficiaryBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    const after = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.isAbove(after.depositTs.toNumber(), start.depositTs.toNumber());
+    assert.equal(after.loyaltyStartTs.toNumber(), start.loyaltyStartTs.toNumber());
+    await withdrawC(150);
+  });
+
+  // =========================================================================
+  // 23. WITHDRAW RATE LIMIT
+  // =========================================================================
+
+  it("NEGATIVE: Withdrawals above the window budget are rejected", async () => {
+    await program.methods.updateWithdrawLimit(new anchor.BN(3600), new anchor.BN(50), new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+
+    await depositC(100);
+    try {
+      await withdrawC(60);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Withdrawal exceeds the remaining budget for this window");
+    }
+  });
+
+  it("POSITIVE: Withdrawals within the window budget go through", async () => {
+    await withdrawC(50);
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.withdrawWindowUsed.toNumber(), 50);
+
+    // Budget khatam: 1 token bhi nahi nikal sakta
+    try {
+      await withdrawC(1);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Withdrawal exceeds the remaining budget for this window");
+    }
+
+    // Quote bhi wahi budget check karta hai (state nahi badalti)
+    try {
+      await quoteC(1);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "WithdrawLimitExceeded");
+    }
+    const cfgAfterQuote = await program.account.globalConfig.fetch(config);
+    assert.equal(cfgAfterQuote.withdrawWindowUsed.toNumber(), 50);
+
+    // Limit band karke baaki nikal lo
+    await program.methods.updateWithdrawLimit(new anchor.BN(0), new anchor.BN(0), new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await withdrawC(50);
+  });
+
+  it("POSITIVE: emergency_withdraw returns the whole principal without settling rewards", async () => {
+    await depositC(100);
+    const before = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    const quote = await quoteC(before.amount.toNumber());
+    const walletBefore = Number((await getAccount(provider.connection, userCTokenAccount)).amount);
+    const cfgBefore = await program.account.globalConfig.fetch(config);
+
+    await program.methods.emergencyWithdraw().accounts({
+      staker: userC.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      recipient: userCTokenAccount,
+      feeVault: feeVault,
+      config: config,
+      referralAccount: referralAccountA,
+      boostAccount: null,
+      insuranceVault: null,
+      stakerBlocklist: blocklistPda(userC.publicKey),
+      recipientBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).signers([userC]).rpc();
+
+    const after = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(after.amount.toNumber(), 0);
+    // Reward settle nahi hua: pending wahi hai jo deposit ke waqt tha
+    assert.equal(after.pendingRewards.toString(), before.pendingRewards.toString());
+    const walletAfter = Number((await getAccount(provider.connection, userCTokenAccount)).amount);
+    assert.equal(walletAfter - walletBefore, quote.userAmount.toNumber());
+    const cfgAfter = await program.account.globalConfig.fetch(config);
+    assert.equal(cfgBefore.totalStaked.sub(cfgAfter.totalStaked).toNumber(), before.amount.toNumber());
+  });
+
+  // =========================================================================
+  // 24. REWARD AIRDROP
+  // =========================================================================
+
+  const airdrop = (entries: { staker: anchor.web3.PublicKey, amount: number }[], stakeInfos: anchor.web3.PublicKey[]) =>
+    program.methods.airdropRewards(
+      entries.map((e) => ({ staker: e.staker, amount: new anchor.BN(e.amount) }))
+    ).accounts({
+      config: config,
+      rewardVault: rewardVault,
+      rewardMint: rewardMint,
+      adminTokenAccount: userARewardAccount,
+      admin: userA.publicKey,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).remainingAccounts(
+      stakeInfos.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+    ).rpc();
+
+  it("NEGATIVE: Airdrop with stake accounts in the wrong order fails", async () => {
+    await mintTo(provider.connection, userA.payer, rewardMint, userARewardAccount, userA.publicKey, 1000);
+    try {
+      await airdrop(
+        [{ staker: userA.publicKey, amount: 300 }, { staker: userC.publicKey, amount: 200 }],
+        [userCStakeInfo, userAStakeInfo]
+      );
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Remaining account is not the stake account of the airdrop staker");
+    }
+  });
+
+  it("POSITIVE: Admin airdrops bonus rewards to several stakers in one transaction", async () => {
+    const aBefore = (await program.account.userStakeInfo.fetch(userAStakeInfo)).pendingRewards.toNumber();
+    const cBefore = (await program.account.userStakeInfo.fetch(userCStakeInfo)).pendingRewards.toNumber();
+    const vaultBefore = Number((await getAccount(provider.connection, rewardVault)).amount);
+
+    await airdrop(
+      [{ staker: userA.publicKey, amount: 300 }, { staker: userC.publicKey, amount: 200 }],
+      [userAStakeInfo, userCStakeInfo]
+    );
+
+    const aAfter = (await program.account.userStakeInfo.fetch(userAStakeInfo)).pendingRewards.toNumber();
+    const cAfter = (await program.account.userStakeInfo.fetch(userCStakeInfo)).pendingRewards.toNumber();
+    assert.equal(aAfter - aBefore, 300);
+    assert.equal(cAfter - cBefore, 200);
+    const vaultAfter = Number((await getAccount(provider.connection, rewardVault)).amount);
+    assert.equal(vaultAfter - vaultBefore, 500); // Admin ne poora total fund kiya
+  });
+
+  // =========================================================================
+  // 25. MERKLE DISTRIBUTOR
+  // =========================================================================
+
+  let distributor: anchor.web3.PublicKey;
+  let distributorVault: anchor.web3.PublicKey;
+  let userCRewardAccount: anchor.web3.PublicKey;
+
+  // Leaf = keccak(index u64 LE || claimant || amount u64 LE), program ke distribution_leaf jaisa
+  const distributionLeaf = (index: number, claimant: anchor.web3.PublicKey, amount: number) =>
+    Buffer.from(keccak_256(Buffer.concat([
+      new anchor.BN(index).toArrayLike(Buffer, "le", 8),
+      claimant.toBuffer(),
+      new anchor.BN(amount).toArrayLike(Buffer, "le", 8),
+    ])));
+  const leafA = distributionLeaf(0, userA.publicKey, 100);
+  const leafC = distributionLeaf(1, userC.publicKey, 50);
+  // Do leaves ka tree: chhota hash pehle (sorted pair)
+  const distributionRoot = Buffer.from(keccak_256(
+    Buffer.compare(leafA, leafC) <= 0 ? Buffer.concat([leafA, leafC]) : Buffer.concat([leafC, leafA])
+  ));
+
+  const claimC = (index: number, amount: number, proof: Buffer[]) =>
+    program.methods.claim(new anchor.BN(index), new anchor.BN(amount), proof.map((p) => Array.from(p))).accounts({
+      claimant: userC.publicKey,
+      distributor: distributor,
+      distributorVault: distributorVault,
+      mint: rewardMint,
+      recipient: userCRewardAccount,
+      claimantBlocklist: blocklistPda(userC.publicKey),
+      recipientBlocklist: blocklistPda(userC.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).signers([userC]).rpc();
+
+  it("POSITIVE: Admin creates a distributor and funds a round", async () => {
+    [distributor] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("distributor"), rewardMint.toBuffer()], program.programId
+    );
+    [distributorVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("distributor_vault"), rewardMint.toBuffer()], program.programId
+    );
+    userCRewardAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, userA.payer, rewardMint, userC.publicKey
+    )).address;
+
+    await program.methods.initializeDistributor().accounts({
+      config: config,
+      distributor: distributor,
+      distributorVault: distributorVault,
+      mint: rewardMint,
+      admin: userA.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY
+    }).rpc();
+
+    await mintTo(provider.connection, userA.payer, rewardMint, userARewardAccount, userA.publicKey, 150);
+    const expiry = Math.floor(Date.now() / 1000) + 3600;
+    await program.methods.setDistributionRound(Array.from(distributionRoot), new anchor.BN(150), new anchor.BN(expiry)).accounts({
+      config: config,
+      distributor: distributor,
+      distributorVault: distributorVault,
+      mint: rewardMint,
+      adminTokenAccount: userARewardAccount,
+      admin: userA.publicKey,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).rpc();
+
+    const acc = await program.account.merkleDistributor.fetch(distributor);
+    assert.equal(acc.round.toNumber(), 1);
+    assert.equal(acc.totalAmount.toNumber(), 150);
+    assert.equal(Number((await getAccount(provider.connection, distributorVault)).amount), 150);
+  });
+
+  it("NEGATIVE: Claim with a wrong amount fails the proof", async () => {
+    try {
+      await claimC(1, 60, [leafA]);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Merkle proof is invalid");
+    }
+  });
+
+  it("POSITIVE: Claimant receives their allocation once", async () => {
+    const before = Number((await getAccount(provider.connection, userCRewardAccount)).amount);
+    await claimC(1, 50, [leafA]);
+    const after = Number((await getAccount(provider.connection, userCRewardAccount)).amount);
+    assert.equal(after - before, 50);
+    const acc = await program.account.merkleDistributor.fetch(distributor);
+    assert.equal(acc.outstandingAmount.toNumber(), 100);
+
+    try {
+      await claimC(1, 50, [leafA]);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "This index has already been claimed");
+    }
+  });
+
+  it("NEGATIVE: Admin cannot claw back before the round expires", async () => {
+    try {
+      await program.methods.clawbackDistribution().accounts({
+        config: config,
+        distributor: distributor,
+        distributorVault: distributorVault,
+        mint: rewardMint,
+        destination: userARewardAccount,
+        admin: userA.publicKey,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      }).rpc();
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Distribution round is still active or has unclaimed funds");
+    }
+  });
+
+  const setRound = (root: Buffer, total: number) =>
+    program.methods.setDistributionRound(
+      Array.from(root), new anchor.BN(total), new anchor.BN(Math.floor(Date.now() / 1000) + 3600)
+    ).accounts({
+      config: config,
+      distributor: distributor,
+      distributorVault: distributorVault,
+      mint: rewardMint,
+      adminTokenAccount: userARewardAccount,
+      admin: userA.publicKey,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).rpc();
+
+  it("POSITIVE: Dust sent to the vault does not block the next round", async () => {
+    const nextLeaf = distributionLeaf(0, userC.publicKey, 10); // Ek leaf ka tree: root = leaf
+    await mintTo(provider.connection, userA.payer, rewardMint, userARewardAccount, userA.publicKey, 10);
+
+    // Round 1 me abhi 100 baaki hain
+    try {
+      await setRound(nextLeaf, 10);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Distribution round is still active or has unclaimed funds");
+    }
+
+    // User A apna hissa le leta hai, phir koi vault me 1 token ka dust bhejta hai
+    await program.methods.claim(new anchor.BN(0), new anchor.BN(100), [Array.from(leafC)]).accounts({
+      claimant: userA.publicKey,
+      distributor: distributor,
+      distributorVault: distributorVault,
+      mint: rewardMint,
+      recipient: userARewardAccount,
+      claimantBlocklist: blocklistPda(userA.publicKey),
+      recipientBlocklist: blocklistPda(userA.publicKey),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).rpc();
+    await transfer(provider.connection, userA.payer, userARewardAccount, distributorVault, userA.publicKey, 1);
+
+    await setRound(nextLeaf, 10);
+    const acc = await program.account.merkleDistributor.fetch(distributor);
+    assert.equal(acc.round.toNumber(), 2);
+    assert.equal(acc.outstandingAmount.toNumber(), 10);
+    assert.equal(Number((await getAccount(provider.connection, distributorVault)).amount), 11);
+  });
+
+  // =========================================================================
+  // 26. POOL MIGRATION
+  // =========================================================================
+
+  // Naya pool version: tests ke liye mock_pool program (same `deposit` interface)
+  const mockPool = anchor.workspace.MockPool as Program<MockPool>;
+  const mockVaultFor = () => anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), mint.toBuffer()], mockPool.programId
+  )[0];
+
+  const migrateC = (targetProgram: anchor.web3.PublicKey) =>
+    program.methods.migratePosition().accounts({
+      staker: userC.publicKey,
+      vault: vault,
+      stakeInfo: userCStakeInfo,
+      mint: mint,
+      stakerTokenAccount: userCTokenAccount,
+      config: config,
+      feeVault: feeVault,
+      referralAccount: referralAccountA,
+      insuranceVault: null,
+      boostAccount: null,
+      stakerBlocklist: blocklistPda(userC.publicKey),
+      targetProgram: targetProgram,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+    }).remainingAccounts([ // mock_pool ke Deposit accounts, usi order me
+      { pubkey: userC.publicKey, isWritable: false, isSigner: true },
+      { pubkey: mockVaultFor(), isWritable: true, isSigner: false },
+      { pubkey: mint, isWritable: false, isSigner: false },
+      { pubkey: userCTokenAccount, isWritable: true, isSigner: false },
+      { pubkey: anchor.utils.token.TOKEN_PROGRAM_ID, isWritable: false, isSigner: false },
+    ]).signers([userC]).rpc();
+
+  it("NEGATIVE: Positions cannot be migrated before the admin sets a target", async () => {
+    await mockPool.methods.initialize().accounts({
+      payer: userA.publicKey,
+      vault: mockVaultFor(),
+      mint: mint,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    try {
+      await migrateC(mockPool.programId);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Target program does not match the pool's migration target");
+    }
+  });
+
+  it("POSITIVE: Live pool migration pays the normal withdraw fee", async () => {
+    await program.methods.setPoolDeprecated(false, mockPool.programId).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await program.methods.updateFee(new anchor.BN(1000)).accounts({ // 10%
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await depositC(100);
+
+    const walletBefore = Number((await getAccount(provider.connection, userCTokenAccount)).amount);
+    const feeBefore = Number((await getAccount(provider.connection, feeVault)).amount);
+    await migrateC(mockPool.programId);
+
+    // 10 fee: 50% referrer (vault me hi), 5 fee vault; 90 naye pool me
+    assert.equal(Number((await getAccount(provider.connection, mockVaultFor())).amount), 90);
+    assert.equal(Number((await getAccount(provider.connection, feeVault)).amount) - feeBefore, 5);
+    assert.equal(Number((await getAccount(provider.connection, userCTokenAccount)).amount), walletBefore);
+    const info = await program.account.userStakeInfo.fetch(userCStakeInfo);
+    assert.equal(info.amount.toNumber(), 0);
+  });
+
+  it("NEGATIVE: Deprecated pool rejects deposits and unknown migration targets", async () => {
+    await depositC(100); // Deprecate hone se pehle position bana lo
+
+    await program.methods.setPoolDeprecated(true, mockPool.programId).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.isTrue(cfg.deprecated);
+    assert.equal(cfg.migrationTarget.toBase58(), mockPool.programId.toBase58());
+
+    try {
+      await depositC(100);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Pool is deprecated");
+    }
+
+    try {
+      await migrateC(anchor.web3.SystemProgram.programId);
+      assert.fail("Should fail");
+    } catch (e) {
+      assert.include(e.message, "Target program does not match the pool's migration target");
+    }
+  });
+
+  it("POSITIVE: Deprecated pool migrates the whole position without a fee", async () => {
+    const feeBefore = Number((await getAccount(provider.connection, feeVault)).amount);
+    await migrateC(mockPool.programId);
+
+    assert.equal(Number((await getAccount(provider.connection, mockVaultFor())).amount), 190);
+    assert.equal(Number((await getAccount(provider.connection, feeVault)).amount), feeBefore);
+
+    await program.methods.setPoolDeprecated(false, anchor.web3.PublicKey.default).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+    await program.methods.updateFee(new anchor.BN(0)).accounts({
+      config: config, admin: userA.publicKey
+    }).rpc();
+  });
+
 });
@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { StakingContract } from "../../target/types/staking_contract";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAccount,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+// Program me ek hi pool (b"config") hai, isliye ye suite apne Test.toml se `anchor test` me
+// alag fresh validator pe chalta hai.
+describe("staking_contract_token_2022", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.StakingContract as Program<StakingContract>;
+  const admin = provider.wallet as anchor.Wallet;
+
+  const TRANSFER_FEE_BPS = 100; // 1% har transfer pe (Token-2022 fee upar round karta hai)
+  const mintKeypair = anchor.web3.Keypair.generate();
+  const mint = mintKeypair.publicKey;
+
+  const [vault] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), mint.toBuffer()], program.programId
+  );
+  const [config] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")], program.programId
+  );
+  const [stakeInfo] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("user"), admin.publicKey.toBuffer()], program.programId
+  );
+  const tokenContractId = new anchor.web3.PublicKey("UY89vX8nRLbuy8LZCZy9ThMbNp1669Bi7Ue9uMwZC6P");
+  const blocklistPda = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("blocked"), wallet.toBuffer()], tokenContractId
+    )[0];
+
+  let stakerTokenAccount: anchor.web3.PublicKey;
+  let feeVault: anchor.web3.PublicKey;
+
+  const balance = async (account: anchor.web3.PublicKey) =>
+    Number((await getAccount(provider.connection, account, undefined, TOKEN_2022_PROGRAM_ID)).amount);
+
+  it("Setup: Transfer-fee mint and a pool for it", async () => {
+    const space = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: mint,
+        space,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint, admin.publicKey, admin.publicKey, TRANSFER_FEE_BPS, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(mint, 6, admin.publicKey, null, TOKEN_2022_PROGRAM_ID),
+    ), [mintKeypair]);
+
+    stakerTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin.payer, mint, admin.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+    )).address;
+    await mintTo(
+      provider.connection, admin.payer, mint, stakerTokenAccount, admin.publicKey, 10_000, [], undefined, TOKEN_2022_PROGRAM_ID
+    );
+    feeVault = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin.payer, mint, anchor.web3.Keypair.generate().publicKey,
+      false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+    )).address;
+
+    await program.methods.initialize(new anchor.BN(0)).accounts({
+      vault: vault,
+      config: config,
+      mint: mint,
+      feeVault: feeVault,
+      payer: admin.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc();
+  });
+
+  it("POSITIVE: Deposit credits what the vault received, not what was sent", async () => {
+    await program.methods.deposit(new anchor.BN(1000)).accounts({
+      staker: admin.publicKey,
+      vault: vault,
+      stakeInfo: stakeInfo,
+      mint: mint,
+      stakerTokenAccount: stakerTokenAccount,
+      config: config,
+      allowlistEntry: null,
+      boostAccount: null,
+      referralAccount: null,
+      stakerBlocklist: blocklistPda(admin.publicKey),
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId
+    }).rpc();
+
+    // 1000 bheje, 1% (10) mint ne rakh liya
+    assert.equal(await balance(vault), 990);
+    const info = await program.account.userStakeInfo.fetch(stakeInfo);
+    assert.equal(info.amount.toNumber(), 990);
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.totalStaked.toNumber(), 990);
+  });
+
+  it("POSITIVE: Withdraw pays out the whole position and the mint takes its fee on the way out", async () => {
+    const before = await balance(stakerTokenAccount);
+    await program.methods.withdraw(new anchor.BN(990)).accounts({
+      staker: admin.publicKey,
+      vault: vault,
+      stakeInfo: stakeInfo,
+      mint: mint,
+      recipient: stakerTokenAccount,
+      feeVault: feeVault,
+      config: config,
+      boostAccount: null,
+      referralAccount: null,
+      insuranceVault: null,
+      stakerBlocklist: blocklistPda(admin.publicKey),
+      recipientBlocklist: blocklistPda(admin.publicKey),
+      tokenProgram: TOKEN_2022_PROGRAM_ID
+    }).rpc();
+
+    // 990 ka 1% = 9.9 -> Token-2022 ceil karke 10 leta hai
+    assert.equal(await balance(stakerTokenAccount) - before, 980);
+    assert.equal(await balance(vault), 0);
+    const info = await program.account.userStakeInfo.fetch(stakeInfo);
+    assert.equal(info.amount.toNumber(), 0);
+    const cfg = await program.account.globalConfig.fetch(config);
+    assert.equal(cfg.totalStaked.toNumber(), 0);
+  });
+});
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { TokenContract } from "../target/types/token_contract";
-import { TOKEN_PROGRAM_ID, createAccount, getAccount } from "@solana/spl-token";
+import { TOKEN_PROGRAM_ID, approve, createAccount, getAccount } from "@solana/spl-token";
 import { assert } from "chai";
 
 describe("token-contract-tests", () => {
@@ -21,10 +21,16 @@ describe("token-contract-tests", () => {
     [Buffer.from("config")],
     program.programId
   );
+  const blocklistPda = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("blocked"), wallet.toBuffer()],
+      program.programId
+    )[0];
 
   // Users & Wallets
   let tokenAccountA: anchor.web3.PublicKey; // Sender
   let tokenAccountB: anchor.web3.PublicKey; // Receiver
+  let userBKeypair: anchor.web3.Keypair;
   let taxWalletKeypair = anchor.web3.Keypair.generate();
   let taxWallet: anchor.web3.PublicKey;
   let hackerKeypair = anchor.web3.Keypair.generate(); // For negative testing
@@ -89,6 +95,7 @@ describe("token-contract-tests", () => {
     assert.equal(configAccount.taxBasisPoints, INITIAL_TAX_BPS);
     assert.ok(configAccount.authority.equals(payer.publicKey));
     assert.ok(configAccount.taxWallet.equals(taxWallet));
+    assert.ok(configAccount.complianceAuthority.equals(payer.publicKey));
     console.log("   -> Config Initialized with 1% Tax");
   });
 
@@ -131,7 +138,7 @@ describe("token-contract-tests", () => {
 
   it("POSITIVE: Transfer with Tax Deduction", async () => {
     // Setup Receiver
-    const userBKeypair = anchor.web3.Keypair.generate();
+    userBKeypair = anchor.web3.Keypair.generate();
     tokenAccountB = await createAccount(
       provider.connection,
       payer.payer,
@@ -153,6 +160,9 @@ describe("token-contract-tests", () => {
         to: tokenAccountB,
         taxWallet: taxWallet, // Must match config
         config: configPda,
+        fromBlocklist: blocklistPda(payer.publicKey),
+        toBlocklist: blocklistPda(userBKeypair.publicKey),
+        authorityBlocklist: blocklistPda(payer.publicKey),
         authority: payer.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -193,6 +203,38 @@ describe("token-contract-tests", () => {
     }
   });
 
+  it("NEGATIVE: Hacker cannot migrate Config", async () => {
+    try {
+      await program.methods
+        .migrateConfig()
+        .accounts({
+          config: configPda,
+          authority: hackerKeypair.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([hackerKeypair])
+        .rpc();
+      assert.fail("Should have failed with ConstraintHasOne");
+    } catch (err) {
+      assert.ok(err.toString().includes("ConstraintHasOne"), "Expected ConstraintHasOne error");
+    }
+  });
+
+  it("POSITIVE: migrate_config leaves an up-to-date Config unchanged", async () => {
+    const before = await provider.connection.getAccountInfo(configPda);
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        config: configPda,
+        authority: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const after = await provider.connection.getAccountInfo(configPda);
+    assert.ok(after.data.equals(before.data));
+    assert.equal(after.lamports, before.lamports);
+  });
+
   it("NEGATIVE: Transfer with Wrong Tax Wallet FAILS", async () => {
     // Create a fake tax wallet
     const fakeTaxWallet = await createAccount(
@@ -210,6 +252,9 @@ describe("token-contract-tests", () => {
           to: tokenAccountB,
           taxWallet: fakeTaxWallet, // PASSING WRONG WALLET
           config: configPda,
+          fromBlocklist: blocklistPda(payer.publicKey),
+          toBlocklist: blocklistPda(userBKeypair.publicKey),
+          authorityBlocklist: blocklistPda(payer.publicKey),
           authority: payer.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -223,4 +268,240 @@ describe("token-contract-tests", () => {
     }
   });
 
+  // ==============================================================================
+  // 🚫 BLOCKLIST (Compliance)
+  // ==============================================================================
+
+  it("NEGATIVE: Hacker cannot block a wallet", async () => {
+    try {
+      await program.methods
+        .blockWallet(userBKeypair.publicKey)
+        .accounts({
+          config: configPda,
+          blockedWallet: blocklistPda(userBKeypair.publicKey),
+          complianceAuthority: hackerKeypair.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([hackerKeypair])
+        .rpc();
+
+      assert.fail("Should have failed with unauthorized error");
+    } catch (err) {
+      assert.ok(err.toString().includes("ConstraintHasOne"), "Expected ConstraintHasOne error");
+      console.log("   -> Blocked: Only the compliance authority can block");
+    }
+  });
+
+  it("NEGATIVE: Transfer to a blocked wallet FAILS", async () => {
+    await program.methods
+      .blockWallet(userBKeypair.publicKey)
+      .accounts({
+        config: configPda,
+        blockedWallet: blocklistPda(userBKeypair.publicKey),
+        complianceAuthority: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const entry = await program.account.blockedWallet.fetch(blocklistPda(userBKeypair.publicKey));
+    assert.ok(entry.wallet.equals(userBKeypair.publicKey));
+
+    try {
+      await program.methods
+        .transferToken(new anchor.BN(10))
+        .accounts({
+          from: tokenAccountA,
+          to: tokenAccountB,
+          taxWallet: taxWallet,
+          config: configPda,
+          fromBlocklist: blocklistPda(payer.publicKey),
+          toBlocklist: blocklistPda(userBKeypair.publicKey),
+          authorityBlocklist: blocklistPda(payer.publicKey),
+          authority: payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have failed with WalletBlocked");
+    } catch (err) {
+      assert.ok(err.toString().includes("WalletBlocked"), "Expected WalletBlocked error");
+      console.log("   -> Blocked: Transfer to blocklisted wallet prevented");
+    }
+  });
+
+  it("POSITIVE: Unblocked wallet can receive again", async () => {
+    await program.methods
+      .unblockWallet()
+      .accounts({
+        config: configPda,
+        blockedWallet: blocklistPda(userBKeypair.publicKey),
+        complianceAuthority: payer.publicKey,
+      })
+      .rpc();
+
+    const before = await getAccount(provider.connection, tokenAccountB);
+    await program.methods
+      .transferToken(new anchor.BN(50))
+      .accounts({
+        from: tokenAccountA,
+        to: tokenAccountB,
+        taxWallet: taxWallet,
+        config: configPda,
+        fromBlocklist: blocklistPda(payer.publicKey),
+        toBlocklist: blocklistPda(userBKeypair.publicKey),
+        authorityBlocklist: blocklistPda(payer.publicKey),
+        authority: payer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, tokenAccountB);
+    assert.equal(Number(after.amount) - Number(before.amount), 49); // 2% tax
+    console.log("   -> Transfer to unblocked wallet succeeded");
+  });
+
+  it("NEGATIVE: A blocked delegate cannot move tokens for an unblocked owner", async () => {
+    await approve(provider.connection, payer.payer, tokenAccountA, hackerKeypair.publicKey, payer.publicKey, 10);
+    await program.methods
+      .blockWallet(hackerKeypair.publicKey)
+      .accounts({
+        config: configPda,
+        blockedWallet: blocklistPda(hackerKeypair.publicKey),
+        complianceAuthority: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .transferToken(new anchor.BN(10))
+        .accounts({
+          from: tokenAccountA,
+          to: tokenAccountB,
+          taxWallet: taxWallet,
+          config: configPda,
+          fromBlocklist: blocklistPda(payer.publicKey),
+          toBlocklist: blocklistPda(userBKeypair.publicKey),
+          authorityBlocklist: blocklistPda(hackerKeypair.publicKey),
+          authority: hackerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([hackerKeypair])
+        .rpc();
+      assert.fail("Should have failed with WalletBlocked");
+    } catch (err) {
+      assert.ok(err.toString().includes("WalletBlocked"), "Expected WalletBlocked error");
+      console.log("   -> Blocked: Blocklisted delegate cannot spend an allowance");
+    }
+
+    await program.methods
+      .unblockWallet()
+      .accounts({
+        config: configPda,
+        blockedWallet: blocklistPda(hackerKeypair.publicKey),
+        complianceAuthority: payer.publicKey,
+      })
+      .rpc();
+  });
+
+  // ==============================================================================
+  // 🧾 QUOTES (Read-only)
+  // ==============================================================================
+
+  it("POSITIVE: quote_transfer returns the same split as transfer_token", async () => {
+    const quote = await program.methods
+      .quoteTransfer(new anchor.BN(TRANSFER_AMOUNT))
+      .accounts({ config: configPda })
+      .view();
+
+    // Tax is 2%: 100 -> 2 tax, 98 received (same as the transfer test above)
+    assert.equal(quote.tax.toNumber(), 2);
+    assert.equal(quote.receiveAmount.toNumber(), 98);
+  });
+
+  // ==============================================================================
+  // 💸 FEE SPLIT (Multiple tax recipients)
+  // ==============================================================================
+
+  let treasuryAccount: anchor.web3.PublicKey;
+  let partnerAccount: anchor.web3.PublicKey;
+
+  it("NEGATIVE: Fee split weights must sum to 10000 bps", async () => {
+    treasuryAccount = await createAccount(provider.connection, payer.payer, mnint, anchor.web3.Keypair.generate().publicKey);
+    partnerAccount = await createAccount(provider.connection, payer.payer, mnint, anchor.web3.Keypair.generate().publicKey);
+
+    try {
+      await program.methods
+        .setFeeSplit([
+          { tokenAccount: treasuryAccount, bps: 6000 },
+          { tokenAccount: partnerAccount, bps: 3000 },
+        ])
+        .accounts({ config: configPda, authority: payer.publicKey })
+        .rpc();
+      assert.fail("Should have failed with InvalidFeeSplit");
+    } catch (err) {
+      assert.ok(err.toString().includes("InvalidFeeSplit"), "Expected InvalidFeeSplit error");
+    }
+  });
+
+  it("POSITIVE: Tax is split across recipients passed as remaining accounts", async () => {
+    await program.methods
+      .setFeeSplit([
+        { tokenAccount: treasuryAccount, bps: 7000 },
+        { tokenAccount: partnerAccount, bps: 3000 },
+      ])
+      .accounts({ config: configPda, authority: payer.publicKey })
+      .rpc();
+
+    await program.methods
+      .transferToken(new anchor.BN(500)) // 2% tax = 10 -> 7 treasury, 3 partner
+      .accounts({
+        from: tokenAccountA,
+        to: tokenAccountB,
+        taxWallet: taxWallet,
+        config: configPda,
+        fromBlocklist: blocklistPda(payer.publicKey),
+        toBlocklist: blocklistPda(userBKeypair.publicKey),
+        authorityBlocklist: blocklistPda(payer.publicKey),
+        authority: payer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: treasuryAccount, isWritable: true, isSigner: false },
+        { pubkey: partnerAccount, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    assert.equal(Number((await getAccount(provider.connection, treasuryAccount)).amount), 7);
+    assert.equal(Number((await getAccount(provider.connection, partnerAccount)).amount), 3);
+  });
+
+  it("NEGATIVE: Transfer without the fee split accounts FAILS", async () => {
+    try {
+      await program.methods
+        .transferToken(new anchor.BN(TRANSFER_AMOUNT))
+        .accounts({
+          from: tokenAccountA,
+          to: tokenAccountB,
+          taxWallet: taxWallet,
+          config: configPda,
+          fromBlocklist: blocklistPda(payer.publicKey),
+          toBlocklist: blocklistPda(userBKeypair.publicKey),
+          authorityBlocklist: blocklistPda(payer.publicKey),
+          authority: payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Should have failed with FeeRecipientsMissing");
+    } catch (err) {
+      assert.ok(err.toString().includes("FeeRecipientsMissing"), "Expected FeeRecipientsMissing error");
+    }
+
+    // Back to a single tax wallet
+    await program.methods
+      .setFeeSplit([])
+      .accounts({ config: configPda, authority: payer.publicKey })
+      .rpc();
+  });
+
 });